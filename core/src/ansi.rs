@@ -0,0 +1,291 @@
+// core/src/ansi.rs - ANSI/VT100 이스케이프 시퀀스 해석 (CSI, SGR 색)
+
+const MAX_PARAMS: usize = 8;
+
+// ANSI 색 순서(R-G-B)와 VGA 색 순서(B-G-R) 변환. 같은 표로 양방향
+const COLOR_MAP: [u8; 8] = [0, 4, 2, 6, 1, 5, 3, 7];
+
+pub fn ansi_to_vga(color: u8) -> u8 {
+    COLOR_MAP[(color & 0x07) as usize] | (color & 0x08)
+}
+
+pub fn vga_to_ansi(color: u8) -> u8 {
+    ansi_to_vga(color)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Erase {
+    ToEnd,   // 0: 커서부터 끝까지
+    ToStart, // 1: 처음부터 커서까지
+    All,     // 2: 전체
+}
+
+// CSI 숫자 인자 (생략된 인자는 0)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Params {
+    values: [u16; MAX_PARAMS],
+    len: usize,
+}
+
+impl Params {
+    const fn new() -> Self {
+        Params { values: [0; MAX_PARAMS], len: 0 }
+    }
+
+    pub fn get(&self, index: usize) -> u16 {
+        if index < self.len { self.values[index] } else { 0 }
+    }
+
+    // 0이나 생략은 1 (커서 이동 횟수, 좌표)
+    fn count(&self, index: usize) -> usize {
+        self.get(index).max(1) as usize
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = u16> + '_ {
+        self.values[..self.len].iter().copied()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Print(char),
+    Sgr(Params),
+    CursorUp(usize),
+    CursorDown(usize),
+    CursorForward(usize),
+    CursorBack(usize),
+    CursorColumn(usize),                  // 0부터
+    CursorTo { col: usize, row: usize },  // 0부터
+    EraseLine(Erase),
+    EraseScreen(Erase),
+    SaveCursor,
+    RestoreCursor,
+    ShowCursor(bool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Ground,
+    Escape,
+    Csi,
+}
+
+// 문자를 하나씩 넣으면 출력할 문자나 실행할 동작을 돌려줌
+#[derive(Debug, Clone, Copy)]
+pub struct Parser {
+    state: State,
+    params: Params,
+    private: bool, // CSI ? ...
+}
+
+impl Parser {
+    pub const fn new() -> Self {
+        Parser { state: State::Ground, params: Params::new(), private: false }
+    }
+
+    pub fn feed(&mut self, ch: char) -> Option<Action> {
+        match self.state {
+            State::Ground if ch == '\x1b' => {
+                self.state = State::Escape;
+                None
+            },
+            State::Ground => Some(Action::Print(ch)),
+            State::Escape => {
+                self.state = State::Ground;
+                match ch {
+                    '[' => {
+                        self.state = State::Csi;
+                        self.params = Params::new();
+                        self.private = false;
+                        None
+                    },
+                    '7' => Some(Action::SaveCursor),
+                    '8' => Some(Action::RestoreCursor),
+                    _ => None, // 지원하지 않는 시퀀스는 버림
+                }
+            },
+            State::Csi => self.csi(ch),
+        }
+    }
+
+    fn csi(&mut self, ch: char) -> Option<Action> {
+        let params = &mut self.params;
+        match ch {
+            '0'..='9' => {
+                if params.len == 0 {
+                    params.len = 1;
+                }
+                let value = &mut params.values[params.len - 1];
+                *value = value.saturating_mul(10).saturating_add(ch as u16 - '0' as u16);
+                None
+            },
+            ';' => {
+                // 넘치는 인자는 마지막 칸에 덮어씀
+                if params.len == 0 {
+                    params.len = 1;
+                }
+                if params.len < MAX_PARAMS {
+                    params.len += 1;
+                }
+                params.values[params.len - 1] = 0;
+                None
+            },
+            '?' => {
+                self.private = true;
+                None
+            },
+            '\x40'..='\x7e' => {
+                self.state = State::Ground;
+                self.dispatch(ch)
+            },
+            _ => {
+                self.state = State::Ground;
+                None
+            },
+        }
+    }
+
+    fn dispatch(&self, final_byte: char) -> Option<Action> {
+        let p = &self.params;
+        if self.private {
+            return match (p.get(0), final_byte) {
+                (25, 'h') => Some(Action::ShowCursor(true)),
+                (25, 'l') => Some(Action::ShowCursor(false)),
+                _ => None,
+            };
+        }
+        let erase = || match p.get(0) {
+            0 => Some(Erase::ToEnd),
+            1 => Some(Erase::ToStart),
+            2 => Some(Erase::All),
+            _ => None,
+        };
+        match final_byte {
+            'm' => Some(Action::Sgr(*p)),
+            'A' => Some(Action::CursorUp(p.count(0))),
+            'B' => Some(Action::CursorDown(p.count(0))),
+            'C' => Some(Action::CursorForward(p.count(0))),
+            'D' => Some(Action::CursorBack(p.count(0))),
+            'G' => Some(Action::CursorColumn(p.count(0) - 1)),
+            'H' | 'f' => Some(Action::CursorTo { col: p.count(1) - 1, row: p.count(0) - 1 }),
+            'K' => erase().map(Action::EraseLine),
+            'J' => erase().map(Action::EraseScreen),
+            's' => Some(Action::SaveCursor),
+            'u' => Some(Action::RestoreCursor),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// SGR 인자를 VGA 속성 바이트(배경 << 4 | 전경)에 적용. 굵게는 밝은 색으로 표시
+pub fn apply_sgr(attr: u8, default: u8, params: &Params) -> u8 {
+    let (mut fg, mut bg) = (attr & 0x0F, attr >> 4);
+    // ESC[m 은 ESC[0m
+    if params.len == 0 {
+        return default;
+    }
+    for value in params.iter() {
+        match value {
+            0 => (fg, bg) = (default & 0x0F, default >> 4),
+            1 => fg |= 0x08,
+            22 => fg &= 0x07,
+            7 => (fg, bg) = (bg, fg),
+            30..=37 => fg = ansi_to_vga((value - 30) as u8) | (fg & 0x08),
+            39 => fg = default & 0x0F,
+            40..=47 => bg = ansi_to_vga((value - 40) as u8),
+            49 => bg = default >> 4,
+            90..=97 => fg = ansi_to_vga((value - 90) as u8) | 0x08,
+            100..=107 => bg = ansi_to_vga((value - 100) as u8) | 0x08,
+            _ => {},
+        }
+    }
+    (bg << 4) | fg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn actions(s: &str) -> Vec<Action> {
+        let mut parser = Parser::new();
+        s.chars().filter_map(|ch| parser.feed(ch)).collect()
+    }
+
+    fn sgr(s: &str) -> Params {
+        match actions(s).as_slice() {
+            [Action::Sgr(params)] => *params,
+            other => panic!("not a single SGR: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn plain_text_passes_through() {
+        assert_eq!(actions("a\n"), [Action::Print('a'), Action::Print('\n')]);
+    }
+
+    #[test]
+    fn cursor_positioning() {
+        assert_eq!(actions("\x1b[5;10H"), [Action::CursorTo { col: 9, row: 4 }]);
+        assert_eq!(actions("\x1b[H"), [Action::CursorTo { col: 0, row: 0 }]);
+        assert_eq!(actions("\x1b[;3f"), [Action::CursorTo { col: 2, row: 0 }]);
+        assert_eq!(actions("\x1b[A\x1b[3B\x1b[2C\x1b[0D"), [
+            Action::CursorUp(1), Action::CursorDown(3), Action::CursorForward(2), Action::CursorBack(1),
+        ]);
+        assert_eq!(actions("\x1b[12G"), [Action::CursorColumn(11)]);
+    }
+
+    #[test]
+    fn erase_and_save() {
+        assert_eq!(actions("\x1b[K\x1b[1K\x1b[2K"), [
+            Action::EraseLine(Erase::ToEnd), Action::EraseLine(Erase::ToStart), Action::EraseLine(Erase::All),
+        ]);
+        assert_eq!(actions("\x1b[2J\x1b[3J"), [Action::EraseScreen(Erase::All)]);
+        assert_eq!(actions("\x1b[s\x1b[u\x1b7\x1b8"), [
+            Action::SaveCursor, Action::RestoreCursor, Action::SaveCursor, Action::RestoreCursor,
+        ]);
+        assert_eq!(actions("\x1b[?25l\x1b[?25h"), [Action::ShowCursor(false), Action::ShowCursor(true)]);
+    }
+
+    #[test]
+    fn unknown_and_broken_sequences_are_dropped() {
+        assert_eq!(actions("\x1b[5zA"), [Action::Print('A')]);
+        assert_eq!(actions("\x1b(B"), [Action::Print('B')]);
+        assert_eq!(actions("\x1b[1\x01x"), [Action::Print('x')]);
+    }
+
+    #[test]
+    fn sgr_colors() {
+        assert_eq!(apply_sgr(0x07, 0x07, &sgr("\x1b[31m")), 0x04);
+        assert_eq!(apply_sgr(0x07, 0x07, &sgr("\x1b[1;34;43m")), 0x69);
+        assert_eq!(apply_sgr(0x07, 0x07, &sgr("\x1b[92;104m")), 0x9A);
+        assert_eq!(apply_sgr(0x1F, 0x07, &sgr("\x1b[0m")), 0x07);
+        assert_eq!(apply_sgr(0x1F, 0x07, &sgr("\x1b[m")), 0x07);
+        assert_eq!(apply_sgr(0x1F, 0x07, &sgr("\x1b[39m")), 0x17);
+        assert_eq!(apply_sgr(0x0F, 0x07, &sgr("\x1b[22m")), 0x07);
+        assert_eq!(apply_sgr(0x1E, 0x07, &sgr("\x1b[7m")), 0xE1);
+    }
+
+    #[test]
+    fn too_many_params_do_not_overflow() {
+        let params = sgr("\x1b[1;2;3;4;5;6;7;8;9;31m");
+        assert_eq!(params.iter().count(), MAX_PARAMS);
+        assert_eq!(params.get(MAX_PARAMS - 1), 31);
+        assert_eq!(sgr("\x1b[99999m").get(0), u16::MAX);
+    }
+
+    #[test]
+    fn color_order() {
+        assert_eq!(ansi_to_vga(1), 4); // 빨강
+        assert_eq!(ansi_to_vga(3), 6); // 노랑 (갈색)
+        assert_eq!(ansi_to_vga(12), 9);
+        for color in 0..16 {
+            assert_eq!(vga_to_ansi(ansi_to_vga(color)), color);
+        }
+    }
+}
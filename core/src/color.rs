@@ -0,0 +1,40 @@
+// core/src/color.rs - VGA 16색 코드 파싱
+
+// 16진수 한 자리 (0-F, 대소문자 무관)
+pub fn parse_color(s: &str) -> Option<u8> {
+    let mut chars = s.chars();
+    let digit = chars.next()?.to_digit(16)?;
+    if chars.next().is_some() {
+        return None;
+    }
+    Some(digit as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decimal_digits() {
+        for (i, s) in ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"].iter().enumerate() {
+            assert_eq!(parse_color(s), Some(i as u8));
+        }
+    }
+
+    #[test]
+    fn hex_letters_any_case() {
+        assert_eq!(parse_color("a"), Some(0xA));
+        assert_eq!(parse_color("A"), Some(0xA));
+        assert_eq!(parse_color("f"), Some(0xF));
+        assert_eq!(parse_color("F"), Some(0xF));
+    }
+
+    #[test]
+    fn rejects_invalid() {
+        assert_eq!(parse_color(""), None);
+        assert_eq!(parse_color("g"), None);
+        assert_eq!(parse_color("-1"), None);
+        assert_eq!(parse_color("10"), None);
+        assert_eq!(parse_color("ㄱ"), None);
+    }
+}
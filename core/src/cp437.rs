@@ -0,0 +1,105 @@
+// core/src/cp437.rs - 유니코드 -> 코드 페이지 437 (VGA 텍스트 모드 / 콘솔 글꼴 순서)
+
+// 0x01-0x1F: 제어 문자 자리에 있는 기호
+const LOW: [char; 31] = [
+    '☺', '☻', '♥', '♦', '♣', '♠', '•', '◘', '○', '◙', '♂', '♀', '♪', '♫', '☼',
+    '►', '◄', '↕', '‼', '¶', '§', '▬', '↨', '↑', '↓', '→', '←', '∟', '↔', '▲', '▼',
+];
+
+// 0x80-0xFF
+const HIGH: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ',
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»',
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐',
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧',
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀',
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{A0}',
+];
+
+// 모양이 같은 다른 코드 포인트
+const ALIASES: [(char, u8); 4] = [
+    ('β', 0xE1), // 그리스 베타 -> ß 자리
+    ('μ', 0xE6), // 그리스 뮤 -> µ 자리
+    ('⌂', 0x7F),
+    ('∑', 0xE4),
+];
+
+// 표시할 수 없는 문자
+pub const UNKNOWN: u8 = 0xFE; // ■
+
+// 출력 가능한 문자 -> CP437 바이트 (제어 문자와 표에 없는 문자는 None)
+pub fn from_char(ch: char) -> Option<u8> {
+    if (' '..='~').contains(&ch) {
+        return Some(ch as u8);
+    }
+    if let Some(i) = LOW.iter().position(|&c| c == ch) {
+        return Some(i as u8 + 1);
+    }
+    if let Some(i) = HIGH.iter().position(|&c| c == ch) {
+        return Some(i as u8 + 0x80);
+    }
+    ALIASES.iter().find(|&&(c, _)| c == ch).map(|&(_, byte)| byte)
+}
+
+// 대체 문자 포함
+pub fn encode(ch: char) -> u8 {
+    from_char(ch).unwrap_or(UNKNOWN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_is_identity() {
+        for byte in 0x20..=0x7Eu8 {
+            assert_eq!(from_char(byte as char), Some(byte));
+        }
+    }
+
+    #[test]
+    fn box_drawing_and_arrows() {
+        assert_eq!(encode('┌'), 0xDA);
+        assert_eq!(encode('─'), 0xC4);
+        assert_eq!(encode('╬'), 0xCE);
+        assert_eq!(encode('█'), 0xDB);
+        assert_eq!(encode('→'), 0x1A);
+        assert_eq!(encode('↑'), 0x18);
+        assert_eq!(encode('☺'), 0x01);
+    }
+
+    #[test]
+    fn latin1_letters() {
+        assert_eq!(encode('é'), 0x82);
+        assert_eq!(encode('Ü'), 0x9A);
+        assert_eq!(encode('ñ'), 0xA4);
+        assert_eq!(encode('°'), 0xF8);
+        assert_eq!(encode('½'), 0xAB);
+        assert_eq!(encode('\u{A0}'), 0xFF);
+    }
+
+    #[test]
+    fn aliases() {
+        assert_eq!(encode('β'), encode('ß'));
+        assert_eq!(encode('μ'), encode('µ'));
+    }
+
+    #[test]
+    fn unmappable_falls_back() {
+        assert_eq!(from_char('한'), None);
+        assert_eq!(from_char('€'), None);
+        assert_eq!(from_char('\n'), None);
+        assert_eq!(from_char('\x7F'), None);
+        assert_eq!(encode('한'), UNKNOWN);
+    }
+
+    #[test]
+    fn table_has_no_duplicates() {
+        let mut seen = std::collections::HashSet::new();
+        for ch in LOW.iter().chain(HIGH.iter()) {
+            assert!(seen.insert(*ch), "{:?} listed twice", ch);
+        }
+    }
+}
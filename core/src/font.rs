@@ -0,0 +1,60 @@
+// core/src/font.rs - 비트맵 글꼴 변환 (글자당 높이만큼의 바이트, 최상위 비트가 왼쪽)
+
+// 위아래 두 줄을 OR로 합쳐 높이를 반으로 (8x16 -> 8x8). 가는 가로선도 사라지지 않음
+// src는 글자당 height줄, dst는 글자당 height / 2줄
+pub fn halve_height(src: &[u8], height: usize, dst: &mut [u8]) {
+    let half = height / 2;
+    if half == 0 {
+        return;
+    }
+    for (glyph, out) in src.chunks(height).zip(dst.chunks_mut(half)) {
+        for (row, bits) in out.iter_mut().enumerate() {
+            let top = glyph.get(row * 2).copied().unwrap_or(0);
+            let bottom = glyph.get(row * 2 + 1).copied().unwrap_or(0);
+            *bits = top | bottom;
+        }
+    }
+}
+
+// 각 줄을 오른쪽으로 한 픽셀 겹쳐 그려 굵게 (글자 크기는 그대로)
+pub fn embolden(src: &[u8], dst: &mut [u8]) {
+    for (out, &bits) in dst.iter_mut().zip(src) {
+        *out = bits | (bits >> 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_row_pairs() {
+        let src = [
+            0x00, 0x18, 0x3C, 0x00, 0xFF, 0x00, 0x81, 0x81,
+            0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80,
+        ];
+        let mut dst = [0u8; 8];
+        halve_height(&src, 16, &mut dst);
+        assert_eq!(dst, [0x18, 0x3C, 0xFF, 0x81, 0x03, 0x0C, 0x30, 0xC0]);
+    }
+
+    #[test]
+    fn converts_every_glyph() {
+        let mut src = [0u8; 16 * 3];
+        src[16] = 0xAA; // 두 번째 글자의 첫 줄
+        src[47] = 0x55; // 세 번째 글자의 마지막 줄
+        let mut dst = [0xFFu8; 8 * 3];
+        halve_height(&src, 16, &mut dst);
+        assert_eq!(dst[..8], [0; 8]);
+        assert_eq!(dst[8], 0xAA);
+        assert_eq!(dst[23], 0x55);
+    }
+
+    #[test]
+    fn embolden_widens_strokes() {
+        let src = [0x80, 0x18, 0x01, 0x00];
+        let mut dst = [0u8; 4];
+        embolden(&src, &mut dst);
+        assert_eq!(dst, [0xC0, 0x1C, 0x01, 0x00]);
+    }
+}
@@ -0,0 +1,102 @@
+// core/src/format.rs - 힙 없이 숫자/시간 문자열 만들기
+
+pub fn format_uptime(hours: u64, minutes: u64, seconds: u64, buf: &mut [u8]) -> &str {
+    let mut pos = 0;
+    let prefix = b"Uptime: ";
+    for &b in prefix {
+        buf[pos] = b;
+        pos += 1;
+    }
+    
+    if hours > 0 {
+        pos += write_num(&mut buf[pos..], hours);
+        buf[pos] = b'h';
+        pos += 1;
+        buf[pos] = b' ';
+        pos += 1;
+    }
+    
+    if minutes > 0 || hours > 0 {
+        pos += write_num(&mut buf[pos..], minutes);
+        buf[pos] = b'm';
+        pos += 1;
+        buf[pos] = b' ';
+        pos += 1;
+    }
+    
+    pos += write_num(&mut buf[pos..], seconds);
+    buf[pos] = b's';
+    pos += 1;
+    
+    core::str::from_utf8(&buf[..pos]).unwrap_or("Uptime: N/A")
+}
+
+pub fn write_num(buf: &mut [u8], mut num: u64) -> usize {
+    if num == 0 {
+        buf[0] = b'0';
+        return 1;
+    }
+    
+    let mut digits = [0u8; 20];
+    let mut count = 0;
+    while num > 0 {
+        digits[count] = (num % 10) as u8 + b'0';
+        num /= 10;
+        count += 1;
+    }
+    
+    for i in 0..count {
+        buf[i] = digits[count - 1 - i];
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn num(n: u64) -> String {
+        let mut buf = [0u8; 20];
+        let len = write_num(&mut buf, n);
+        String::from_utf8(buf[..len].to_vec()).unwrap()
+    }
+
+    fn uptime(hours: u64, minutes: u64, seconds: u64) -> String {
+        let mut buf = [0u8; 64];
+        format_uptime(hours, minutes, seconds, &mut buf).to_string()
+    }
+
+    #[test]
+    fn write_num_digits() {
+        assert_eq!(num(0), "0");
+        assert_eq!(num(7), "7");
+        assert_eq!(num(10), "10");
+        assert_eq!(num(1234567890), "1234567890");
+        assert_eq!(num(u64::MAX), "18446744073709551615");
+    }
+
+    #[test]
+    fn write_num_returns_length() {
+        let mut buf = [b'x'; 8];
+        assert_eq!(write_num(&mut buf, 305), 3);
+        assert_eq!(&buf, b"305xxxxx");
+    }
+
+    #[test]
+    fn uptime_seconds_only() {
+        assert_eq!(uptime(0, 0, 0), "Uptime: 0s");
+        assert_eq!(uptime(0, 0, 42), "Uptime: 42s");
+    }
+
+    #[test]
+    fn uptime_minutes() {
+        assert_eq!(uptime(0, 5, 3), "Uptime: 5m 3s");
+        assert_eq!(uptime(0, 1, 0), "Uptime: 1m 0s");
+    }
+
+    #[test]
+    fn uptime_hours_show_minutes() {
+        assert_eq!(uptime(2, 0, 9), "Uptime: 2h 0m 9s");
+        assert_eq!(uptime(100, 59, 59), "Uptime: 100h 59m 59s");
+    }
+}
@@ -0,0 +1,297 @@
+// core/src/hangul.rs - 두벌식 한글 조합기 (자모 -> 완성형 음절)
+
+// 초성 19자, 중성 21자, 종성 27자 (+ 받침 없음)
+const CHOSEONG: [char; 19] = [
+    'ㄱ', 'ㄲ', 'ㄴ', 'ㄷ', 'ㄸ', 'ㄹ', 'ㅁ', 'ㅂ', 'ㅃ', 'ㅅ',
+    'ㅆ', 'ㅇ', 'ㅈ', 'ㅉ', 'ㅊ', 'ㅋ', 'ㅌ', 'ㅍ', 'ㅎ',
+];
+
+const JONGSEONG: [char; 27] = [
+    'ㄱ', 'ㄲ', 'ㄳ', 'ㄴ', 'ㄵ', 'ㄶ', 'ㄷ', 'ㄹ', 'ㄺ', 'ㄻ',
+    'ㄼ', 'ㄽ', 'ㄾ', 'ㄿ', 'ㅀ', 'ㅁ', 'ㅂ', 'ㅄ', 'ㅅ', 'ㅆ',
+    'ㅇ', 'ㅈ', 'ㅊ', 'ㅋ', 'ㅌ', 'ㅍ', 'ㅎ',
+];
+
+// 겹모음: (첫째, 둘째, 결과)
+const COMPOUND_VOWELS: [(char, char, char); 7] = [
+    ('ㅗ', 'ㅏ', 'ㅘ'), ('ㅗ', 'ㅐ', 'ㅙ'), ('ㅗ', 'ㅣ', 'ㅚ'),
+    ('ㅜ', 'ㅓ', 'ㅝ'), ('ㅜ', 'ㅔ', 'ㅞ'), ('ㅜ', 'ㅣ', 'ㅟ'),
+    ('ㅡ', 'ㅣ', 'ㅢ'),
+];
+
+// 겹받침: (첫째, 둘째, 결과)
+const COMPOUND_FINALS: [(char, char, char); 11] = [
+    ('ㄱ', 'ㅅ', 'ㄳ'), ('ㄴ', 'ㅈ', 'ㄵ'), ('ㄴ', 'ㅎ', 'ㄶ'),
+    ('ㄹ', 'ㄱ', 'ㄺ'), ('ㄹ', 'ㅁ', 'ㄻ'), ('ㄹ', 'ㅂ', 'ㄼ'),
+    ('ㄹ', 'ㅅ', 'ㄽ'), ('ㄹ', 'ㅌ', 'ㄾ'), ('ㄹ', 'ㅍ', 'ㄿ'),
+    ('ㄹ', 'ㅎ', 'ㅀ'), ('ㅂ', 'ㅅ', 'ㅄ'),
+];
+
+const SYLLABLE_BASE: u32 = 0xAC00;
+const VOWEL_BASE: u32 = 0x314F; // ㅏ
+
+pub fn is_jamo(ch: char) -> bool {
+    ('\u{3131}'..='\u{3163}').contains(&ch)
+}
+
+fn is_vowel(ch: char) -> bool {
+    ('\u{314F}'..='\u{3163}').contains(&ch)
+}
+
+fn choseong_index(ch: char) -> Option<u32> {
+    CHOSEONG.iter().position(|&c| c == ch).map(|i| i as u32)
+}
+
+fn jongseong_index(ch: char) -> Option<u32> {
+    JONGSEONG.iter().position(|&c| c == ch).map(|i| i as u32 + 1)
+}
+
+fn combine(table: &[(char, char, char)], first: char, second: char) -> Option<char> {
+    table.iter()
+        .find(|&&(a, b, _)| a == first && b == second)
+        .map(|&(_, _, result)| result)
+}
+
+fn split(table: &[(char, char, char)], compound: char) -> Option<(char, char)> {
+    table.iter()
+        .find(|&&(_, _, c)| c == compound)
+        .map(|&(a, b, _)| (a, b))
+}
+
+pub struct HangulIme {
+    cho: Option<char>,
+    jung: Option<char>,
+    jong: Option<char>,
+}
+
+impl Default for HangulIme {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HangulIme {
+    pub const fn new() -> Self {
+        HangulIme {
+            cho: None,
+            jung: None,
+            jong: None,
+        }
+    }
+
+    // 조합 중인 글자 (화면 표시용)
+    pub fn preedit(&self) -> Option<char> {
+        match (self.cho, self.jung) {
+            (Some(cho), Some(jung)) => {
+                let cho = choseong_index(cho)?;
+                let jung = jung as u32 - VOWEL_BASE;
+                let jong = self.jong.and_then(jongseong_index).unwrap_or(0);
+                char::from_u32(SYLLABLE_BASE + (cho * 21 + jung) * 28 + jong)
+            },
+            (Some(cho), None) => Some(cho),
+            (None, Some(jung)) => Some(jung),
+            (None, None) => None,
+        }
+    }
+
+    // 자모 하나 입력 -> 완성되어 확정된 글자가 있으면 반환
+    pub fn feed(&mut self, jamo: char) -> Option<char> {
+        if is_vowel(jamo) {
+            self.feed_vowel(jamo)
+        } else {
+            self.feed_consonant(jamo)
+        }
+    }
+
+    fn feed_consonant(&mut self, c: char) -> Option<char> {
+        match (self.cho, self.jung, self.jong) {
+            (Some(_), Some(_), None) if jongseong_index(c).is_some() => {
+                self.jong = Some(c);
+                None
+            },
+            (Some(_), Some(_), Some(jong)) => {
+                if let Some(compound) = combine(&COMPOUND_FINALS, jong, c) {
+                    self.jong = Some(compound);
+                    None
+                } else {
+                    self.start_with(Some(c), None)
+                }
+            },
+            (None, None, _) => {
+                self.cho = Some(c);
+                None
+            },
+            _ => self.start_with(Some(c), None),
+        }
+    }
+
+    fn feed_vowel(&mut self, v: char) -> Option<char> {
+        match (self.cho, self.jung, self.jong) {
+            (_, None, _) => {
+                self.jung = Some(v);
+                None
+            },
+            (_, Some(jung), None) => {
+                if let Some(compound) = combine(&COMPOUND_VOWELS, jung, v) {
+                    self.jung = Some(compound);
+                    None
+                } else {
+                    self.start_with(None, Some(v))
+                }
+            },
+            (_, Some(_), Some(jong)) => {
+                // 받침이 다음 음절의 초성으로 넘어감 (닭 + ㅏ -> 달가)
+                let (keep, moved) = match split(&COMPOUND_FINALS, jong) {
+                    Some((first, second)) => (Some(first), second),
+                    None => (None, jong),
+                };
+                self.jong = keep;
+                self.start_with(Some(moved), Some(v))
+            },
+        }
+    }
+
+    // 현재 글자를 확정하고 새 글자를 시작
+    fn start_with(&mut self, cho: Option<char>, jung: Option<char>) -> Option<char> {
+        let committed = self.preedit();
+        self.cho = cho;
+        self.jung = jung;
+        self.jong = None;
+        committed
+    }
+
+    // 조합 중인 글자를 확정
+    pub fn flush(&mut self) -> Option<char> {
+        self.start_with(None, None)
+    }
+
+    // 마지막 자모 하나 지우기 -> 조합 중이 아니면 false
+    pub fn backspace(&mut self) -> bool {
+        if let Some(jong) = self.jong {
+            self.jong = split(&COMPOUND_FINALS, jong).map(|(first, _)| first);
+        } else if let Some(jung) = self.jung {
+            self.jung = split(&COMPOUND_VOWELS, jung).map(|(first, _)| first);
+        } else if self.cho.is_some() {
+            self.cho = None;
+        } else {
+            return false;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 자모를 차례로 넣고 마지막에 확정한 문자열
+    fn compose(jamo: &str) -> String {
+        let mut ime = HangulIme::new();
+        let mut out = String::new();
+        for ch in jamo.chars() {
+            out.extend(ime.feed(ch));
+        }
+        out.extend(ime.flush());
+        out
+    }
+
+    #[test]
+    fn jamo_ranges() {
+        assert!(is_jamo('ㄱ'));
+        assert!(is_jamo('ㅣ'));
+        assert!(!is_jamo('가'));
+        assert!(!is_jamo('a'));
+    }
+
+    #[test]
+    fn simple_syllables() {
+        assert_eq!(compose("ㅎㅏㄴㄱㅡㄹ"), "한글");
+        assert_eq!(compose("ㄱㅏ"), "가");
+        assert_eq!(compose("ㅎㅣㅎ"), "힣");
+    }
+
+    #[test]
+    fn final_moves_to_next_syllable() {
+        assert_eq!(compose("ㄷㅏㄹㄱㅏ"), "달가");
+        assert_eq!(compose("ㅇㅏㄴㅈㅏ"), "안자");
+    }
+
+    #[test]
+    fn compound_vowels() {
+        assert_eq!(compose("ㅇㅗㅏㅅㅓ"), "와서");
+        assert_eq!(compose("ㅇㅡㅣㅅㅏ"), "의사");
+        assert_eq!(compose("ㅂㅜㅔㄹㅂ"), "뷃");
+    }
+
+    #[test]
+    fn compound_finals() {
+        assert_eq!(compose("ㄷㅏㄹㅁ"), "닮");
+        // 겹받침 뒤에 모음이 오면 둘째 자음만 넘어감
+        assert_eq!(compose("ㄷㅏㄹㄱㅡㄴ"), "달근");
+        assert_eq!(compose("ㅇㅓㅂㅅㅇㅓ"), "없어");
+    }
+
+    #[test]
+    fn lone_jamo() {
+        assert_eq!(compose("ㄱ"), "ㄱ");
+        assert_eq!(compose("ㅏ"), "ㅏ");
+        assert_eq!(compose("ㄱㄴ"), "ㄱㄴ");
+        assert_eq!(compose("ㅏㅏ"), "ㅏㅏ");
+    }
+
+    #[test]
+    fn double_consonant_not_final() {
+        // ㄸ은 받침이 될 수 없으므로 새 글자로
+        assert_eq!(compose("ㄱㅏㄸㅏ"), "가따");
+    }
+
+    #[test]
+    fn preedit_tracks_state() {
+        let mut ime = HangulIme::new();
+        assert_eq!(ime.preedit(), None);
+        ime.feed('ㅎ');
+        assert_eq!(ime.preedit(), Some('ㅎ'));
+        ime.feed('ㅏ');
+        assert_eq!(ime.preedit(), Some('하'));
+        ime.feed('ㄴ');
+        assert_eq!(ime.preedit(), Some('한'));
+    }
+
+    #[test]
+    fn backspace_removes_one_jamo() {
+        let mut ime = HangulIme::new();
+        for ch in ['ㄷ', 'ㅏ', 'ㄹ', 'ㄱ'] {
+            ime.feed(ch);
+        }
+        assert_eq!(ime.preedit(), Some('닭'));
+        assert!(ime.backspace());
+        assert_eq!(ime.preedit(), Some('달'));
+        assert!(ime.backspace());
+        assert_eq!(ime.preedit(), Some('다'));
+        assert!(ime.backspace());
+        assert_eq!(ime.preedit(), Some('ㄷ'));
+        assert!(ime.backspace());
+        assert_eq!(ime.preedit(), None);
+        assert!(!ime.backspace());
+    }
+
+    #[test]
+    fn backspace_splits_compound_vowel() {
+        let mut ime = HangulIme::new();
+        for ch in ['ㄱ', 'ㅗ', 'ㅏ'] {
+            ime.feed(ch);
+        }
+        assert_eq!(ime.preedit(), Some('과'));
+        assert!(ime.backspace());
+        assert_eq!(ime.preedit(), Some('고'));
+    }
+
+    #[test]
+    fn flush_resets() {
+        let mut ime = HangulIme::new();
+        ime.feed('ㅁ');
+        assert_eq!(ime.flush(), Some('ㅁ'));
+        assert_eq!(ime.flush(), None);
+        assert_eq!(ime.preedit(), None);
+    }
+}
@@ -0,0 +1,161 @@
+// core/src/keymap.rs - 키보드 배열 테이블 (US QWERTY, Dvorak, 한글 두벌식)
+use crate::scancode::KeyCode;
+use crate::scancode::KeyCode::*;
+
+// (키, 기본, Shift)
+pub type KeyTable = &'static [(KeyCode, char, char)];
+
+pub struct Layout {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub keys: KeyTable,
+    pub jamo: Option<KeyTable>, // 한글 입력 모드에서 쓰는 자모 테이블
+}
+
+impl Layout {
+    pub fn lookup(&self, code: KeyCode) -> Option<(char, char)> {
+        lookup(self.keys, code)
+    }
+
+    pub fn lookup_jamo(&self, code: KeyCode) -> Option<(char, char)> {
+        lookup(self.jamo?, code)
+    }
+
+    pub fn has_hangul(&self) -> bool {
+        self.jamo.is_some()
+    }
+}
+
+fn lookup(table: KeyTable, code: KeyCode) -> Option<(char, char)> {
+    table.iter()
+        .find(|&&(key, _, _)| key == code)
+        .map(|&(_, normal, shifted)| (normal, shifted))
+}
+
+// 모든 배열에 공통인 키
+pub fn common_key(code: KeyCode) -> Option<char> {
+    match code {
+        Escape => Some('\x1b'),
+        Backspace => Some('\x08'),
+        Tab => Some('\t'),
+        Enter | KeypadEnter => Some('\n'),
+        Space => Some(' '),
+        _ => None,
+    }
+}
+
+const US_KEYS: KeyTable = &[
+    (Key1, '1', '!'), (Key2, '2', '@'), (Key3, '3', '#'), (Key4, '4', '$'),
+    (Key5, '5', '%'), (Key6, '6', '^'), (Key7, '7', '&'), (Key8, '8', '*'),
+    (Key9, '9', '('), (Key0, '0', ')'), (Minus, '-', '_'), (Equals, '=', '+'),
+    (Q, 'q', 'Q'), (W, 'w', 'W'), (E, 'e', 'E'), (R, 'r', 'R'), (T, 't', 'T'),
+    (Y, 'y', 'Y'), (U, 'u', 'U'), (I, 'i', 'I'), (O, 'o', 'O'), (P, 'p', 'P'),
+    (LeftBracket, '[', '{'), (RightBracket, ']', '}'),
+    (A, 'a', 'A'), (S, 's', 'S'), (D, 'd', 'D'), (F, 'f', 'F'), (G, 'g', 'G'),
+    (H, 'h', 'H'), (J, 'j', 'J'), (K, 'k', 'K'), (L, 'l', 'L'),
+    (Semicolon, ';', ':'), (Quote, '\'', '"'), (Backtick, '`', '~'),
+    (Backslash, '\\', '|'), (Oem102, '\\', '|'),
+    (Z, 'z', 'Z'), (X, 'x', 'X'), (C, 'c', 'C'), (V, 'v', 'V'), (B, 'b', 'B'),
+    (N, 'n', 'N'), (M, 'm', 'M'),
+    (Comma, ',', '<'), (Period, '.', '>'), (Slash, '/', '?'),
+];
+
+const DVORAK_KEYS: KeyTable = &[
+    (Key1, '1', '!'), (Key2, '2', '@'), (Key3, '3', '#'), (Key4, '4', '$'),
+    (Key5, '5', '%'), (Key6, '6', '^'), (Key7, '7', '&'), (Key8, '8', '*'),
+    (Key9, '9', '('), (Key0, '0', ')'), (Minus, '[', '{'), (Equals, ']', '}'),
+    (Q, '\'', '"'), (W, ',', '<'), (E, '.', '>'), (R, 'p', 'P'), (T, 'y', 'Y'),
+    (Y, 'f', 'F'), (U, 'g', 'G'), (I, 'c', 'C'), (O, 'r', 'R'), (P, 'l', 'L'),
+    (LeftBracket, '/', '?'), (RightBracket, '=', '+'),
+    (A, 'a', 'A'), (S, 'o', 'O'), (D, 'e', 'E'), (F, 'u', 'U'), (G, 'i', 'I'),
+    (H, 'd', 'D'), (J, 'h', 'H'), (K, 't', 'T'), (L, 'n', 'N'),
+    (Semicolon, 's', 'S'), (Quote, '-', '_'), (Backtick, '`', '~'),
+    (Backslash, '\\', '|'), (Oem102, '\\', '|'),
+    (Z, ';', ':'), (X, 'q', 'Q'), (C, 'j', 'J'), (V, 'k', 'K'), (B, 'x', 'X'),
+    (N, 'b', 'B'), (M, 'm', 'M'),
+    (Comma, 'w', 'W'), (Period, 'v', 'V'), (Slash, 'z', 'Z'),
+];
+
+// 두벌식 자모 (호환용 자모 U+3131..U+3163)
+const DUBEOLSIK_JAMO: KeyTable = &[
+    (Q, 'ㅂ', 'ㅃ'), (W, 'ㅈ', 'ㅉ'), (E, 'ㄷ', 'ㄸ'), (R, 'ㄱ', 'ㄲ'), (T, 'ㅅ', 'ㅆ'),
+    (Y, 'ㅛ', 'ㅛ'), (U, 'ㅕ', 'ㅕ'), (I, 'ㅑ', 'ㅑ'), (O, 'ㅐ', 'ㅒ'), (P, 'ㅔ', 'ㅖ'),
+    (A, 'ㅁ', 'ㅁ'), (S, 'ㄴ', 'ㄴ'), (D, 'ㅇ', 'ㅇ'), (F, 'ㄹ', 'ㄹ'), (G, 'ㅎ', 'ㅎ'),
+    (H, 'ㅗ', 'ㅗ'), (J, 'ㅓ', 'ㅓ'), (K, 'ㅏ', 'ㅏ'), (L, 'ㅣ', 'ㅣ'),
+    (Z, 'ㅋ', 'ㅋ'), (X, 'ㅌ', 'ㅌ'), (C, 'ㅊ', 'ㅊ'), (V, 'ㅍ', 'ㅍ'),
+    (B, 'ㅠ', 'ㅠ'), (N, 'ㅜ', 'ㅜ'), (M, 'ㅡ', 'ㅡ'),
+];
+
+pub const US: Layout = Layout {
+    name: "us",
+    description: "US QWERTY",
+    keys: US_KEYS,
+    jamo: None,
+};
+
+pub const DVORAK: Layout = Layout {
+    name: "dvorak",
+    description: "US Dvorak",
+    keys: DVORAK_KEYS,
+    jamo: None,
+};
+
+pub const KOREAN: Layout = Layout {
+    name: "ko",
+    description: "Korean 2-set (Han/Eng: Right Alt or Shift+Space)",
+    keys: US_KEYS,
+    jamo: Some(DUBEOLSIK_JAMO),
+};
+
+pub const LAYOUTS: [&Layout; 3] = [&US, &DVORAK, &KOREAN];
+
+pub fn find(name: &str) -> Option<&'static Layout> {
+    LAYOUTS.iter().copied().find(|layout| layout.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_by_name() {
+        for layout in LAYOUTS {
+            assert_eq!(find(layout.name).map(|l| l.name), Some(layout.name));
+        }
+        assert!(find("").is_none());
+        assert!(find("US").is_none());
+    }
+
+    #[test]
+    fn only_korean_has_hangul() {
+        assert!(!US.has_hangul());
+        assert!(!DVORAK.has_hangul());
+        assert!(KOREAN.has_hangul());
+        assert_eq!(KOREAN.lookup_jamo(A), Some(('ㅁ', 'ㅁ')));
+        assert_eq!(US.lookup_jamo(A), None);
+    }
+
+    #[test]
+    fn tables_have_no_duplicate_keys() {
+        for table in [US_KEYS, DVORAK_KEYS, DUBEOLSIK_JAMO] {
+            for (i, &(key, _, _)) in table.iter().enumerate() {
+                assert!(table[i + 1..].iter().all(|&(other, _, _)| other != key), "{:?}", key);
+            }
+        }
+    }
+
+    #[test]
+    fn layouts_cover_same_keys() {
+        for &(key, _, _) in US_KEYS {
+            assert!(DVORAK.lookup(key).is_some(), "{:?}", key);
+        }
+    }
+
+    #[test]
+    fn common_keys_are_layout_independent() {
+        assert_eq!(common_key(Enter), Some('\n'));
+        assert_eq!(common_key(KeypadEnter), Some('\n'));
+        assert_eq!(common_key(Space), Some(' '));
+        assert_eq!(common_key(A), None);
+    }
+}
@@ -0,0 +1,14 @@
+// core/src/lib.rs - 하드웨어와 무관한 커널 로직 (호스트에서 cargo test로 검증)
+#![cfg_attr(not(test), no_std)]
+
+pub mod ansi;
+pub mod color;
+pub mod cp437;
+pub mod font;
+pub mod format;
+pub mod hangul;
+pub mod keymap;
+pub mod raster;
+pub mod scancode;
+pub mod shell;
+pub mod watch;
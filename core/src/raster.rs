@@ -0,0 +1,116 @@
+// core/src/raster.rs - 그래픽 모드 도형 계산 (선분의 점, 화면에 맞춘 사각형)
+
+// 브레즌햄 선분: 두 끝점을 포함한 모든 점
+pub struct LinePoints {
+    x: i32,
+    y: i32,
+    x1: i32,
+    y1: i32,
+    dx: i32,
+    dy: i32, // 음수
+    sx: i32,
+    sy: i32,
+    err: i32,
+    done: bool,
+}
+
+impl LinePoints {
+    pub fn new(x0: i32, y0: i32, x1: i32, y1: i32) -> Self {
+        let dx = (x1 - x0).abs();
+        let dy = -(y1 - y0).abs();
+        LinePoints {
+            x: x0,
+            y: y0,
+            x1,
+            y1,
+            dx,
+            dy,
+            sx: if x0 < x1 { 1 } else { -1 },
+            sy: if y0 < y1 { 1 } else { -1 },
+            err: dx + dy,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for LinePoints {
+    type Item = (i32, i32);
+
+    fn next(&mut self) -> Option<(i32, i32)> {
+        if self.done {
+            return None;
+        }
+        let point = (self.x, self.y);
+        if self.x == self.x1 && self.y == self.y1 {
+            self.done = true;
+            return Some(point);
+        }
+        let e2 = 2 * self.err;
+        if e2 >= self.dy {
+            self.err += self.dy;
+            self.x += self.sx;
+        }
+        if e2 <= self.dx {
+            self.err += self.dx;
+            self.y += self.sy;
+        }
+        Some(point)
+    }
+}
+
+// (x, y)에서 w x h인 사각형 중 width x height 화면 안쪽 부분 (x, y, w, h). 화면 밖이면 None
+pub fn clip_rect(x: i32, y: i32, w: usize, h: usize, width: usize, height: usize) -> Option<(usize, usize, usize, usize)> {
+    let clip = |start: i32, len: usize, limit: usize| {
+        let end = (start as i64 + len as i64).min(limit as i64);
+        let start = (start as i64).max(0);
+        (start < end).then(|| (start as usize, (end - start) as usize))
+    };
+    let (x, w) = clip(x, w, width)?;
+    let (y, h) = clip(y, h, height)?;
+    Some((x, y, w, h))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(x0: i32, y0: i32, x1: i32, y1: i32) -> Vec<(i32, i32)> {
+        LinePoints::new(x0, y0, x1, y1).collect()
+    }
+
+    #[test]
+    fn straight_lines() {
+        assert_eq!(line(2, 5, 5, 5), [(2, 5), (3, 5), (4, 5), (5, 5)]);
+        assert_eq!(line(1, 3, 1, 0), [(1, 3), (1, 2), (1, 1), (1, 0)]);
+        assert_eq!(line(4, 4, 4, 4), [(4, 4)]);
+    }
+
+    #[test]
+    fn diagonal_and_steep() {
+        assert_eq!(line(0, 0, 3, 3), [(0, 0), (1, 1), (2, 2), (3, 3)]);
+        assert_eq!(line(0, 0, 1, 3), [(0, 0), (0, 1), (1, 2), (1, 3)]);
+        assert_eq!(line(0, 0, 4, 2), [(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)]);
+    }
+
+    #[test]
+    fn reversed_endpoints_cover_same_length() {
+        let forward = line(-3, 7, 10, -2);
+        let backward = line(10, -2, -3, 7);
+        assert_eq!(forward.len(), backward.len());
+        assert_eq!(forward.first(), backward.last());
+        // 연속된 점은 한 칸씩만 떨어짐
+        for pair in forward.windows(2) {
+            assert!((pair[0].0 - pair[1].0).abs() <= 1 && (pair[0].1 - pair[1].1).abs() <= 1);
+        }
+    }
+
+    #[test]
+    fn clipping() {
+        assert_eq!(clip_rect(10, 10, 20, 5, 320, 200), Some((10, 10, 20, 5)));
+        assert_eq!(clip_rect(-5, -2, 10, 10, 320, 200), Some((0, 0, 5, 8)));
+        assert_eq!(clip_rect(310, 195, 20, 20, 320, 200), Some((310, 195, 10, 5)));
+        assert_eq!(clip_rect(-10, 0, 10, 10, 320, 200), None);
+        assert_eq!(clip_rect(320, 0, 10, 10, 320, 200), None);
+        assert_eq!(clip_rect(0, 0, 0, 10, 320, 200), None);
+    }
+}
@@ -0,0 +1,614 @@
+// core/src/scancode.rs - 스캔코드 세트 1 -> 키 이벤트 변환 (하드웨어 무관)
+use crate::keymap::{self, Layout};
+
+// LED 비트 (0xED 명령의 데이터 바이트)
+pub const LED_SCROLL_LOCK: u8 = 0x01;
+pub const LED_NUM_LOCK: u8 = 0x02;
+pub const LED_CAPS_LOCK: u8 = 0x04;
+
+// 제어 문자
+pub const CTRL_C: char = '\x03'; // 포그라운드 명령 중단
+pub const CTRL_D: char = '\x04'; // 입력 끝 (EOF)
+
+// 물리 키 코드
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyCode {
+    Escape,
+    Key1, Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9, Key0,
+    Minus, Equals, Backspace, Tab,
+    Q, W, E, R, T, Y, U, I, O, P,
+    LeftBracket, RightBracket, Enter,
+    A, S, D, F, G, H, J, K, L,
+    Semicolon, Quote, Backtick, Backslash,
+    Z, X, C, V, B, N, M,
+    Comma, Period, Slash, Space, Oem102,
+    LeftShift, RightShift, LeftCtrl, RightCtrl, LeftAlt, RightAlt,
+    LeftGui, RightGui, Menu,
+    CapsLock, NumLock, ScrollLock,
+    F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+    Keypad0, Keypad1, Keypad2, Keypad3, Keypad4,
+    Keypad5, Keypad6, Keypad7, Keypad8, Keypad9,
+    KeypadPeriod, KeypadPlus, KeypadMinus, KeypadStar, KeypadSlash, KeypadEnter,
+    Insert, Delete, Home, End, PageUp, PageDown,
+    Up, Down, Left, Right,
+    PrintScreen, Pause,
+    HanEng, Hanja, // 한국어 키보드 전용 키 (0xF2, 0xF1)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyState {
+    Pressed,
+    Released,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Modifiers {
+    pub lshift: bool,
+    pub rshift: bool,
+    pub lctrl: bool,
+    pub rctrl: bool,
+    pub lalt: bool,
+    pub ralt: bool,
+    pub caps_lock: bool,
+    pub num_lock: bool,
+    pub scroll_lock: bool,
+}
+
+impl Default for Modifiers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Modifiers {
+    pub const fn new() -> Self {
+        Modifiers {
+            lshift: false,
+            rshift: false,
+            lctrl: false,
+            rctrl: false,
+            lalt: false,
+            ralt: false,
+            caps_lock: false,
+            num_lock: false,
+            scroll_lock: false,
+        }
+    }
+
+    pub fn shift(&self) -> bool {
+        self.lshift || self.rshift
+    }
+
+    pub fn ctrl(&self) -> bool {
+        self.lctrl || self.rctrl
+    }
+
+    pub fn alt(&self) -> bool {
+        self.lalt || self.ralt
+    }
+
+    pub fn leds(&self) -> u8 {
+        let mut leds = 0;
+        if self.scroll_lock { leds |= LED_SCROLL_LOCK; }
+        if self.num_lock { leds |= LED_NUM_LOCK; }
+        if self.caps_lock { leds |= LED_CAPS_LOCK; }
+        leds
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyEvent {
+    pub code: KeyCode,
+    pub state: KeyState,
+    pub modifiers: Modifiers, // 이벤트 처리 후의 수식 키 상태
+    pub ch: Option<char>,
+}
+
+impl KeyEvent {
+    pub fn is_pressed(&self) -> bool {
+        self.state == KeyState::Pressed
+    }
+}
+
+pub struct Keyboard {
+    modifiers: Modifiers,
+    extended: bool,        // 0xE0 접두사 수신
+    pause_remaining: u8,   // 0xE1 Pause 시퀀스의 남은 바이트 수
+    layout: &'static Layout,
+    hangul: bool,          // 한/영 상태 (true = 한글 자모 입력)
+}
+
+impl Default for Keyboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Keyboard {
+    pub const fn new() -> Self {
+        Keyboard {
+            modifiers: Modifiers::new(),
+            extended: false,
+            pause_remaining: 0,
+            layout: &keymap::US,
+            hangul: false,
+        }
+    }
+
+    pub fn modifiers(&self) -> Modifiers {
+        self.modifiers
+    }
+
+    pub fn layout(&self) -> &'static Layout {
+        self.layout
+    }
+
+    pub fn set_layout(&mut self, layout: &'static Layout) {
+        self.layout = layout;
+        self.hangul = false;
+    }
+
+    pub fn hangul_mode(&self) -> bool {
+        self.hangul
+    }
+
+    // 스캔코드 하나를 처리하고, 키 이벤트가 완성되면 반환
+    pub fn process(&mut self, scancode: u8) -> Option<KeyEvent> {
+        // 컨트롤러 응답 바이트 무시 (ACK, RESEND, ECHO, 오류)
+        if matches!(scancode, 0x00 | 0xFA | 0xFE | 0xEE | 0xFF) {
+            return None;
+        }
+
+        // Pause: E1 1D 45 E1 9D C5 (release 코드 없음)
+        if self.pause_remaining > 0 {
+            self.pause_remaining -= 1;
+            if self.pause_remaining == 0 {
+                return Some(self.event(KeyCode::Pause, KeyState::Pressed));
+            }
+            return None;
+        }
+
+        match scancode {
+            0xE0 => { self.extended = true; return None; },
+            0xE1 => { self.pause_remaining = 5; return None; },
+            // 한/영, 한자 키는 release 코드 없이 한 바이트만 보냄
+            0xF1 => return Some(self.event(KeyCode::Hanja, KeyState::Pressed)),
+            0xF2 => {
+                self.toggle_hangul();
+                return Some(self.event(KeyCode::HanEng, KeyState::Pressed));
+            },
+            _ => {},
+        }
+
+        let extended = core::mem::replace(&mut self.extended, false);
+        let state = if scancode & 0x80 != 0 { KeyState::Released } else { KeyState::Pressed };
+        let pressed = state == KeyState::Pressed;
+
+        let code = if extended {
+            extended_key(scancode & 0x7F)?
+        } else {
+            base_key(scancode & 0x7F)?
+        };
+
+        // 수식 키 / 토글 키
+        match code {
+            KeyCode::LeftShift => self.modifiers.lshift = pressed,
+            KeyCode::RightShift => self.modifiers.rshift = pressed,
+            KeyCode::LeftCtrl => self.modifiers.lctrl = pressed,
+            KeyCode::RightCtrl => self.modifiers.rctrl = pressed,
+            KeyCode::LeftAlt => self.modifiers.lalt = pressed,
+            KeyCode::RightAlt => self.modifiers.ralt = pressed,
+            KeyCode::CapsLock if pressed => self.modifiers.caps_lock = !self.modifiers.caps_lock,
+            KeyCode::NumLock if pressed => self.modifiers.num_lock = !self.modifiers.num_lock,
+            KeyCode::ScrollLock if pressed => self.modifiers.scroll_lock = !self.modifiers.scroll_lock,
+            _ => {},
+        }
+
+        // 한/영 전환: 오른쪽 Alt 또는 Shift+Space
+        if pressed && (code == KeyCode::RightAlt || (code == KeyCode::Space && self.modifiers.shift()))
+            && self.toggle_hangul() {
+            return Some(KeyEvent { code, state, modifiers: self.modifiers, ch: None });
+        }
+
+        // Num Lock이 꺼져 있으면 키패드는 탐색 키로 동작
+        let code = if self.modifiers.num_lock { code } else { keypad_navigation(code) };
+
+        Some(self.event(code, state))
+    }
+
+    // 한글 배열일 때만 전환됨
+    fn toggle_hangul(&mut self) -> bool {
+        if self.layout.has_hangul() {
+            self.hangul = !self.hangul;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn event(&self, code: KeyCode, state: KeyState) -> KeyEvent {
+        let ch = if state == KeyState::Pressed { self.decode_char(code) } else { None };
+        KeyEvent { code, state, modifiers: self.modifiers, ch }
+    }
+
+    fn decode_char(&self, code: KeyCode) -> Option<char> {
+        if let Some(ch) = keymap::common_key(code).or_else(|| keypad_char(code)) {
+            return Some(ch);
+        }
+
+        // 한글 모드: Ctrl 조합이 아니면 자모 입력
+        if self.hangul && !self.modifiers.ctrl() {
+            if let Some((normal, shifted)) = self.layout.lookup_jamo(code) {
+                return Some(if self.modifiers.shift() { shifted } else { normal });
+            }
+        }
+
+        let (normal, shifted) = self.layout.lookup(code)?;
+
+        if normal.is_ascii_lowercase() {
+            // Ctrl+문자 -> 제어 문자 (Ctrl+A = 0x01)
+            if self.modifiers.ctrl() {
+                return Some((normal as u8 - b'a' + 1) as char);
+            }
+            let upper = self.modifiers.shift() != self.modifiers.caps_lock;
+            return Some(if upper { shifted } else { normal });
+        }
+
+        Some(if self.modifiers.shift() { shifted } else { normal })
+    }
+}
+
+// 접두사 없는 스캔코드 -> 키 코드
+fn base_key(code: u8) -> Option<KeyCode> {
+    use KeyCode::*;
+    let key = match code {
+        0x01 => Escape,
+        0x02 => Key1, 0x03 => Key2, 0x04 => Key3, 0x05 => Key4, 0x06 => Key5,
+        0x07 => Key6, 0x08 => Key7, 0x09 => Key8, 0x0A => Key9, 0x0B => Key0,
+        0x0C => Minus, 0x0D => Equals, 0x0E => Backspace, 0x0F => Tab,
+        0x10 => Q, 0x11 => W, 0x12 => E, 0x13 => R, 0x14 => T,
+        0x15 => Y, 0x16 => U, 0x17 => I, 0x18 => O, 0x19 => P,
+        0x1A => LeftBracket, 0x1B => RightBracket, 0x1C => Enter, 0x1D => LeftCtrl,
+        0x1E => A, 0x1F => S, 0x20 => D, 0x21 => F, 0x22 => G,
+        0x23 => H, 0x24 => J, 0x25 => K, 0x26 => L,
+        0x27 => Semicolon, 0x28 => Quote, 0x29 => Backtick,
+        0x2A => LeftShift, 0x2B => Backslash,
+        0x2C => Z, 0x2D => X, 0x2E => C, 0x2F => V, 0x30 => B, 0x31 => N, 0x32 => M,
+        0x33 => Comma, 0x34 => Period, 0x35 => Slash, 0x36 => RightShift,
+        0x37 => KeypadStar, 0x38 => LeftAlt, 0x39 => Space, 0x3A => CapsLock,
+        0x3B => F1, 0x3C => F2, 0x3D => F3, 0x3E => F4, 0x3F => F5,
+        0x40 => F6, 0x41 => F7, 0x42 => F8, 0x43 => F9, 0x44 => F10,
+        0x45 => NumLock, 0x46 => ScrollLock,
+        0x47 => Keypad7, 0x48 => Keypad8, 0x49 => Keypad9, 0x4A => KeypadMinus,
+        0x4B => Keypad4, 0x4C => Keypad5, 0x4D => Keypad6, 0x4E => KeypadPlus,
+        0x4F => Keypad1, 0x50 => Keypad2, 0x51 => Keypad3,
+        0x52 => Keypad0, 0x53 => KeypadPeriod,
+        0x56 => Oem102, 0x57 => F11, 0x58 => F12,
+        _ => return None,
+    };
+    Some(key)
+}
+
+// 0xE0 접두사 스캔코드 -> 키 코드
+fn extended_key(code: u8) -> Option<KeyCode> {
+    use KeyCode::*;
+    let key = match code {
+        0x1C => KeypadEnter,
+        0x1D => RightCtrl,
+        0x35 => KeypadSlash,
+        0x37 => PrintScreen,
+        0x38 => RightAlt,
+        0x46 => Pause, // Ctrl+Break
+        0x47 => Home, 0x48 => Up, 0x49 => PageUp,
+        0x4B => Left, 0x4D => Right,
+        0x4F => End, 0x50 => Down, 0x51 => PageDown,
+        0x52 => Insert, 0x53 => Delete,
+        0x5B => LeftGui, 0x5C => RightGui, 0x5D => Menu,
+        // 0x2A/0x36: PrintScreen 등에 붙는 가짜 Shift -> 무시
+        _ => return None,
+    };
+    Some(key)
+}
+
+fn keypad_navigation(code: KeyCode) -> KeyCode {
+    use KeyCode::*;
+    match code {
+        Keypad7 => Home, Keypad8 => Up, Keypad9 => PageUp,
+        Keypad4 => Left, Keypad6 => Right,
+        Keypad1 => End, Keypad2 => Down, Keypad3 => PageDown,
+        Keypad0 => Insert, KeypadPeriod => Delete,
+        other => other,
+    }
+}
+
+// 키패드 문자 (탐색 키로 바뀐 경우는 여기 오지 않음)
+fn keypad_char(code: KeyCode) -> Option<char> {
+    use KeyCode::*;
+    match code {
+        Keypad7 => Some('7'), Keypad8 => Some('8'), Keypad9 => Some('9'),
+        Keypad4 => Some('4'), Keypad5 => Some('5'), Keypad6 => Some('6'),
+        Keypad1 => Some('1'), Keypad2 => Some('2'), Keypad3 => Some('3'),
+        Keypad0 => Some('0'), KeypadPeriod => Some('.'),
+        KeypadStar => Some('*'), KeypadMinus => Some('-'),
+        KeypadPlus => Some('+'), KeypadSlash => Some('/'),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 눌렀다 떼고 입력된 문자 반환
+    fn type_key(keyboard: &mut Keyboard, scancode: u8) -> Option<char> {
+        let ch = keyboard.process(scancode).and_then(|event| event.ch);
+        keyboard.process(scancode | 0x80);
+        ch
+    }
+
+    fn type_str(keyboard: &mut Keyboard, scancodes: &[u8]) -> String {
+        scancodes.iter().filter_map(|&sc| type_key(keyboard, sc)).collect()
+    }
+
+    #[test]
+    fn every_base_scancode_round_trips() {
+        // 정의된 기본 키는 누름/뗌 모두 같은 키 코드
+        for scancode in 0x01..0x59u8 {
+            if let Some(code) = base_key(scancode) {
+                let mut keyboard = Keyboard::new();
+                let press = keyboard.process(scancode).unwrap();
+                let release = keyboard.process(scancode | 0x80).unwrap();
+                assert_eq!(press.state, KeyState::Pressed);
+                assert_eq!(release.state, KeyState::Released);
+                assert_eq!(release.ch, None);
+                if keyboard.modifiers().num_lock {
+                    assert_eq!(press.code, code);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_scancodes() {
+        let mut keyboard = Keyboard::new();
+        assert!(keyboard.process(0x54).is_none());
+        assert!(keyboard.process(0x7F).is_none());
+        keyboard.process(0xE0);
+        assert!(keyboard.process(0x2A).is_none()); // 가짜 Shift
+        // 접두사는 한 번만 적용
+        assert_eq!(keyboard.process(0x1C).unwrap().code, KeyCode::Enter);
+    }
+
+    #[test]
+    fn us_letters_and_digits() {
+        let mut keyboard = Keyboard::new();
+        let text = type_str(&mut keyboard, &[0x23, 0x12, 0x26, 0x26, 0x18, 0x39, 0x02, 0x03, 0x0B]);
+        assert_eq!(text, "hello 120");
+    }
+
+    #[test]
+    fn shift_symbols() {
+        let mut keyboard = Keyboard::new();
+        keyboard.process(0x2A);
+        let text = type_str(&mut keyboard, &[0x02, 0x03, 0x0C, 0x0D, 0x1A, 0x27, 0x28, 0x29, 0x2B, 0x33, 0x35]);
+        keyboard.process(0xAA);
+        assert_eq!(text, "!@_+{:\"~|<?");
+    }
+
+    #[test]
+    fn either_shift_works() {
+        let mut keyboard = Keyboard::new();
+        keyboard.process(0x36);
+        assert_eq!(type_key(&mut keyboard, 0x10), Some('Q'));
+        keyboard.process(0xB6);
+        assert_eq!(type_key(&mut keyboard, 0x10), Some('q'));
+    }
+
+    #[test]
+    fn caps_lock_only_letters() {
+        let mut keyboard = Keyboard::new();
+        type_key(&mut keyboard, 0x3A);
+        assert!(keyboard.modifiers().caps_lock);
+        assert_eq!(type_key(&mut keyboard, 0x1E), Some('A'));
+        assert_eq!(type_key(&mut keyboard, 0x02), Some('1'));
+        keyboard.process(0x2A);
+        assert_eq!(type_key(&mut keyboard, 0x1E), Some('a'));
+        assert_eq!(type_key(&mut keyboard, 0x02), Some('!'));
+        keyboard.process(0xAA);
+        type_key(&mut keyboard, 0x3A);
+        assert!(!keyboard.modifiers().caps_lock);
+    }
+
+    #[test]
+    fn ctrl_letters() {
+        let mut keyboard = Keyboard::new();
+        keyboard.process(0x1D);
+        assert_eq!(type_key(&mut keyboard, 0x1E), Some('\x01'));
+        assert_eq!(type_key(&mut keyboard, 0x2E), Some(CTRL_C));
+        assert_eq!(type_key(&mut keyboard, 0x20), Some(CTRL_D));
+        assert_eq!(type_key(&mut keyboard, 0x2C), Some('\x1a'));
+        keyboard.process(0x9D);
+
+        // 오른쪽 Ctrl (E0 1D)
+        keyboard.process(0xE0);
+        assert_eq!(keyboard.process(0x1D).unwrap().code, KeyCode::RightCtrl);
+        assert!(keyboard.modifiers().ctrl());
+        assert_eq!(type_key(&mut keyboard, 0x2E), Some(CTRL_C));
+    }
+
+    #[test]
+    fn modifier_state_in_event() {
+        let mut keyboard = Keyboard::new();
+        keyboard.process(0x38);
+        let event = keyboard.process(0x0F).unwrap();
+        assert!(event.modifiers.alt());
+        assert!(!event.modifiers.shift());
+        assert!(event.is_pressed());
+    }
+
+    #[test]
+    fn common_keys() {
+        let mut keyboard = Keyboard::new();
+        assert_eq!(type_key(&mut keyboard, 0x01), Some('\x1b'));
+        assert_eq!(type_key(&mut keyboard, 0x0E), Some('\x08'));
+        assert_eq!(type_key(&mut keyboard, 0x0F), Some('\t'));
+        assert_eq!(type_key(&mut keyboard, 0x1C), Some('\n'));
+        assert_eq!(type_key(&mut keyboard, 0x39), Some(' '));
+    }
+
+    #[test]
+    fn extended_navigation_keys() {
+        let cases = [
+            (0x47, KeyCode::Home), (0x48, KeyCode::Up), (0x49, KeyCode::PageUp),
+            (0x4B, KeyCode::Left), (0x4D, KeyCode::Right), (0x4F, KeyCode::End),
+            (0x50, KeyCode::Down), (0x51, KeyCode::PageDown),
+            (0x52, KeyCode::Insert), (0x53, KeyCode::Delete),
+        ];
+        let mut keyboard = Keyboard::new();
+        for (scancode, code) in cases {
+            keyboard.process(0xE0);
+            let event = keyboard.process(scancode).unwrap();
+            assert_eq!(event.code, code);
+            assert_eq!(event.ch, None);
+            keyboard.process(0xE0);
+            assert_eq!(keyboard.process(scancode | 0x80).unwrap().state, KeyState::Released);
+        }
+    }
+
+    #[test]
+    fn keypad_enter_and_slash() {
+        let mut keyboard = Keyboard::new();
+        keyboard.process(0xE0);
+        assert_eq!(keyboard.process(0x1C).unwrap().ch, Some('\n'));
+        keyboard.process(0xE0);
+        assert_eq!(keyboard.process(0x35).unwrap().ch, Some('/'));
+    }
+
+    #[test]
+    fn num_lock_switches_keypad() {
+        let mut keyboard = Keyboard::new();
+        if keyboard.modifiers().num_lock {
+            type_key(&mut keyboard, 0x45);
+        }
+        let event = keyboard.process(0x47).unwrap();
+        assert_eq!(event.code, KeyCode::Home);
+        assert_eq!(event.ch, None);
+        // 탐색 키로 바뀌지 않는 키패드 키
+        assert_eq!(type_key(&mut keyboard, 0x4E), Some('+'));
+
+        type_key(&mut keyboard, 0x45);
+        assert!(keyboard.modifiers().num_lock);
+        let event = keyboard.process(0x47).unwrap();
+        assert_eq!(event.code, KeyCode::Keypad7);
+        assert_eq!(event.ch, Some('7'));
+        assert_eq!(type_key(&mut keyboard, 0x53), Some('.'));
+    }
+
+    #[test]
+    fn leds_follow_lock_keys() {
+        let mut keyboard = Keyboard::new();
+        let base = keyboard.modifiers().leds();
+        type_key(&mut keyboard, 0x3A);
+        assert_eq!(keyboard.modifiers().leds(), base | LED_CAPS_LOCK);
+        type_key(&mut keyboard, 0x46);
+        assert_eq!(keyboard.modifiers().leds(), base | LED_CAPS_LOCK | LED_SCROLL_LOCK);
+        type_key(&mut keyboard, 0x3A);
+        assert_eq!(keyboard.modifiers().leds(), base | LED_SCROLL_LOCK);
+    }
+
+    #[test]
+    fn pause_sequence() {
+        let mut keyboard = Keyboard::new();
+        for scancode in [0xE1, 0x1D, 0x45, 0xE1, 0x9D] {
+            assert!(keyboard.process(scancode).is_none());
+        }
+        let event = keyboard.process(0xC5).unwrap();
+        assert_eq!(event.code, KeyCode::Pause);
+        assert!(event.is_pressed());
+        // 시퀀스 안의 1D/45가 Ctrl, Num Lock으로 처리되지 않음
+        assert!(!keyboard.modifiers().ctrl());
+        assert_eq!(type_key(&mut keyboard, 0x1E), Some('a'));
+    }
+
+    #[test]
+    fn controller_bytes_ignored() {
+        let mut keyboard = Keyboard::new();
+        for scancode in [0x00, 0xFA, 0xFE, 0xEE, 0xFF] {
+            assert!(keyboard.process(scancode).is_none());
+        }
+    }
+
+    #[test]
+    fn dvorak_layout() {
+        let mut keyboard = Keyboard::new();
+        keyboard.set_layout(&keymap::DVORAK);
+        assert_eq!(keyboard.layout().name, "dvorak");
+        let text = type_str(&mut keyboard, &[0x24, 0x20, 0x19, 0x19, 0x1F]);
+        assert_eq!(text, "hello");
+        keyboard.process(0x2A);
+        assert_eq!(type_key(&mut keyboard, 0x10), Some('"'));
+    }
+
+    #[test]
+    fn hangul_toggle_keys() {
+        let mut keyboard = Keyboard::new();
+        keyboard.set_layout(&keymap::KOREAN);
+        assert!(!keyboard.hangul_mode());
+
+        assert_eq!(keyboard.process(0xF2).unwrap().code, KeyCode::HanEng);
+        assert!(keyboard.hangul_mode());
+
+        // 오른쪽 Alt
+        keyboard.process(0xE0);
+        let event = keyboard.process(0x38).unwrap();
+        assert_eq!(event.code, KeyCode::RightAlt);
+        assert!(!keyboard.hangul_mode());
+        keyboard.process(0xE0);
+        keyboard.process(0xB8);
+
+        // Shift+Space
+        keyboard.process(0x2A);
+        assert_eq!(keyboard.process(0x39).unwrap().ch, None);
+        assert!(keyboard.hangul_mode());
+    }
+
+    #[test]
+    fn hangul_jamo_input() {
+        let mut keyboard = Keyboard::new();
+        keyboard.set_layout(&keymap::KOREAN);
+        keyboard.process(0xF2);
+        let text = type_str(&mut keyboard, &[0x22, 0x25, 0x1F, 0x13, 0x32, 0x21]);
+        assert_eq!(text, "ㅎㅏㄴㄱㅡㄹ");
+
+        keyboard.process(0x2A);
+        assert_eq!(type_key(&mut keyboard, 0x12), Some('ㄸ'));
+        assert_eq!(type_key(&mut keyboard, 0x18), Some('ㅒ'));
+        keyboard.process(0xAA);
+
+        // Ctrl 조합과 숫자는 영문 그대로
+        assert_eq!(type_key(&mut keyboard, 0x02), Some('1'));
+        keyboard.process(0x1D);
+        assert_eq!(type_key(&mut keyboard, 0x2E), Some(CTRL_C));
+    }
+
+    #[test]
+    fn set_layout_resets_hangul() {
+        let mut keyboard = Keyboard::new();
+        keyboard.set_layout(&keymap::KOREAN);
+        keyboard.process(0xF2);
+        keyboard.set_layout(&keymap::KOREAN);
+        assert!(!keyboard.hangul_mode());
+
+        keyboard.set_layout(&keymap::US);
+        keyboard.process(0xF2);
+        assert!(!keyboard.hangul_mode());
+    }
+
+    #[test]
+    fn hanja_key() {
+        let mut keyboard = Keyboard::new();
+        let event = keyboard.process(0xF1).unwrap();
+        assert_eq!(event.code, KeyCode::Hanja);
+        assert_eq!(event.ch, None);
+    }
+}
@@ -0,0 +1,592 @@
+// core/src/shell.rs - 확장된 버전
+use crate::color::parse_color;
+use crate::keymap::{self, Layout};
+use crate::watch::{self, Watch, WatchKind};
+use log::LevelFilter;
+
+pub struct Shell {
+    buffer: [u8; 256],
+    len: usize,
+    cursor: usize, // 줄 편집 커서 위치 (0..=len)
+    boot_time: u64, // 부팅 시간 저장
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileCommand {
+    Start,
+    Stop,
+    Report,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FontStyle {
+    Bios, // 부팅 때의 BIOS 글꼴
+    Bold, // BIOS 글꼴을 굵게
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchCommand {
+    List,
+    Set(Watch),
+    Clear(usize), // 디버그 레지스터 번호 (0-3)
+}
+
+// Print는 힙 없이 텍스트를 돌려주므로 크기가 큼
+#[allow(clippy::large_enum_variant)]
+pub enum ShellResult {
+    Output(&'static str),
+    MultiOutput(&'static [&'static str]), // 여러 줄 출력
+    Clear,
+    Print([u8; 256], usize),
+    Shutdown,
+    Reboot,
+    CpuInfo,
+    MemInfo,
+    SysInfo,
+    DateTime,
+    Uptime(u64), // 현재 틱 전달
+    BgColor(u8), // 배경색 코드
+    MouseTest,
+    Sleep(u64), // 대기할 틱 수 (Ctrl+C로 중단)
+    KbdLayout(Option<&'static Layout>), // None이면 현재 배열 표시
+    Dmesg(LevelFilter), // 이 수준 이하의 커널 로그 표시
+    Debugger, // int3로 GDB 스텁 진입
+    Profile(ProfileCommand),
+    BootLog, // 부팅 단계별 시간
+    Watch(WatchCommand),
+    Int3, // 브레이크포인트 핸들러 시험
+    Mode(Option<(usize, usize)>), // 텍스트 모드 (열, 행). None이면 목록
+    GfxTest(usize, usize), // 그래픽 모드 (너비, 높이) 그리기 시험
+    Font(FontStyle), // 텍스트 모드 글꼴 교체
+    Empty,
+}
+
+// "80x50" -> (80, 50)
+fn parse_size(s: &str) -> Option<(usize, usize)> {
+    let (cols, rows) = s.split_once(['x', 'X'])?;
+    Some((cols.parse().ok()?, rows.parse().ok()?))
+}
+
+const WATCH_USAGE: &str = "Usage: watch [<addr> [w|rw|x] [1|2|4|8] | del <n>]";
+
+// watch 인자: 없음 -> 목록, del <n>, <주소> [종류] [길이]
+fn parse_watch(addr: &str, kind: &str, len: &str) -> ShellResult {
+    if addr.is_empty() {
+        return ShellResult::Watch(WatchCommand::List);
+    }
+    if addr == "del" {
+        return match kind.parse::<usize>() {
+            Ok(slot) if slot < 4 => ShellResult::Watch(WatchCommand::Clear(slot)),
+            _ => ShellResult::Output("Watchpoint number must be 0-3"),
+        };
+    }
+
+    let addr = watch::parse_addr(addr);
+    let kind = if kind.is_empty() { Some(WatchKind::Write) } else { WatchKind::parse(kind) };
+    // 길이를 생략하면 데이터는 8바이트, 실행은 1바이트
+    let len = match (len, kind) {
+        ("", Some(WatchKind::Execute)) => Some(1),
+        ("", _) => Some(8),
+        (len, _) => len.parse::<u8>().ok(),
+    };
+    match (addr, kind, len) {
+        (Some(addr), Some(kind), Some(len)) => match Watch::new(addr, kind, len) {
+            Ok(w) => ShellResult::Watch(WatchCommand::Set(w)),
+            Err(e) => ShellResult::Output(e.message()),
+        },
+        _ => ShellResult::Output(WATCH_USAGE),
+    }
+}
+
+impl Default for Shell {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shell {
+    pub const fn new() -> Self {
+        Shell {
+            buffer: [0; 256],
+            len: 0,
+            cursor: 0,
+            boot_time: 0,
+        }
+    }
+    
+    pub fn set_boot_time(&mut self, ticks: u64) {
+        self.boot_time = ticks;
+    }
+    
+    // UTF-8로 저장 (한글 입력 지원)
+    pub fn add_char(&mut self, ch: char) {
+        let mut encoded = [0u8; 4];
+        let bytes = ch.encode_utf8(&mut encoded).as_bytes();
+        let n = bytes.len();
+        if self.len + n <= 255 {
+            self.buffer.copy_within(self.cursor..self.len, self.cursor + n);
+            self.buffer[self.cursor..self.cursor + n].copy_from_slice(bytes);
+            self.len += n;
+            self.cursor += n;
+        }
+    }
+    
+    pub fn backspace(&mut self) {
+        if self.cursor > 0 {
+            let start = self.prev_boundary();
+            self.remove_range(start, self.cursor);
+            self.cursor = start;
+        }
+    }
+    
+    // Delete 키: 커서 위치의 문자 삭제
+    pub fn delete(&mut self) {
+        if self.cursor < self.len {
+            let end = self.next_boundary();
+            self.remove_range(self.cursor, end);
+        }
+    }
+    
+    fn remove_range(&mut self, start: usize, end: usize) {
+        self.buffer.copy_within(end..self.len, start);
+        let removed = end - start;
+        self.len -= removed;
+        self.buffer[self.len..self.len + removed].fill(0);
+    }
+    
+    // 커서 앞/뒤 문자 경계 (UTF-8 연속 바이트 건너뛰기)
+    fn prev_boundary(&self) -> usize {
+        let mut pos = self.cursor - 1;
+        while pos > 0 && self.buffer[pos] & 0xC0 == 0x80 {
+            pos -= 1;
+        }
+        pos
+    }
+    
+    fn next_boundary(&self) -> usize {
+        let mut pos = self.cursor + 1;
+        while pos < self.len && self.buffer[pos] & 0xC0 == 0x80 {
+            pos += 1;
+        }
+        pos
+    }
+    
+    pub fn move_left(&mut self) {
+        if self.cursor > 0 {
+            self.cursor = self.prev_boundary();
+        }
+    }
+    
+    pub fn move_right(&mut self) {
+        if self.cursor < self.len {
+            self.cursor = self.next_boundary();
+        }
+    }
+    
+    pub fn move_home(&mut self) {
+        self.cursor = 0;
+    }
+    
+    pub fn move_end(&mut self) {
+        self.cursor = self.len;
+    }
+    
+    // 커서 위치 (바이트 단위)
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+    
+    pub fn execute(&mut self, current_ticks: u64) -> ShellResult {
+        let cmd = core::str::from_utf8(&self.buffer[..self.len])
+            .unwrap_or("");
+        
+        let parts: [&str; 8] = {
+            let mut p = [""; 8];
+            for (i, part) in cmd.split_whitespace().enumerate() {
+                if i >= 8 { break; }
+                p[i] = part;
+            }
+            p
+        };
+        
+        let result = match parts[0] {
+            "help" => {
+                const LINES: &[&str] = &[
+                    "Available commands:",
+                    "  help      - Show this message",
+                    "  clear     - Clear screen",
+                    "  memtest   - Test memory allocator",
+                    "  print     - Print text",
+                    "  version   - Show OS version",
+                    "  shutdown  - Shutdown (QEMU only)",
+                    "  reboot    - Reboot system",
+                    "  date      - Show current date",
+                    "  time      - Show current time",
+                    "  uptime    - Show uptime",
+                    "  bgcolor   - Change background color (0-F)",
+                    "  cpuinfo   - Show CPU information",
+                    "  meminfo   - Show memory usage",
+                    "  sysinfo   - Show system information",
+                    "  kbdlayout - Show/set keyboard layout",
+                    "  mousetest - Show live mouse cursor",
+                    "  sleep     - Wait N seconds (Ctrl+C cancels)",
+                    "  dmesg     - Show kernel log (error/warn/info/debug/trace)",
+                    "  gdb       - Break into GDB stub on COM2",
+                    "  profile   - Sample kernel time (start|stop|report)",
+                    "  bootlog   - Show boot stage timeline",
+                    "  watch     - Hardware watchpoint (<addr> [w|rw|x] [len], del <n>)",
+                    "  int3      - Trigger a breakpoint and show the frame",
+                    "  mode      - List/set text mode (80x25, 80x50, 90x60)",
+                    "  gfxtest   - Draw in graphics mode (320x200, 640x480)",
+                    "  font      - Switch text font (bios, bold)",
+                ];
+                ShellResult::MultiOutput(LINES)
+            },
+            "clear" => ShellResult::Clear,
+            "memtest" => ShellResult::MemInfo,
+            "version" => ShellResult::Output("AerogelOS v0.1.0 - Polling Mode"),
+            "shutdown" => ShellResult::Shutdown,
+            "reboot" => ShellResult::Reboot,
+            "date" => ShellResult::DateTime,
+            "time" => ShellResult::DateTime,
+            "uptime" => ShellResult::Uptime(current_ticks - self.boot_time),
+            "cpuinfo" => ShellResult::CpuInfo,
+            "meminfo" => ShellResult::MemInfo,
+            "sysinfo" => ShellResult::SysInfo,
+            "bgcolor" if !parts[1].is_empty() => match parse_color(parts[1]) {
+                Some(color) => ShellResult::BgColor(color),
+                None => ShellResult::Output("Invalid color! Use 0-F"),
+            },
+            "print" if !parts[1].is_empty() => {
+                // "print " 이후의 모든 텍스트를 합침
+                if let Some(text_start) = cmd.find("print ") {
+                    let start_pos = text_start + 6; // "print " 길이
+                    let text = if start_pos < cmd.len() {
+                        &cmd[start_pos..]
+                    } else {
+                        ""
+                    };
+                    let mut print_buf = [0u8; 256];
+                    let len = text.len().min(255);
+                    if len > 0 {
+                        print_buf[..len].copy_from_slice(&text.as_bytes()[..len]);
+                        ShellResult::Print(print_buf, len)
+                    } else {
+                        ShellResult::Output("Usage: print <text>")
+                    }
+                } else {
+                    ShellResult::Output("Usage: print <text>")
+                }
+            },
+            "print" => ShellResult::Output("Usage: print <text>"),
+            "kbdlayout" if !parts[1].is_empty() => {
+                match keymap::find(parts[1]) {
+                    Some(layout) => ShellResult::KbdLayout(Some(layout)),
+                    None => ShellResult::Output("Unknown layout! Use us, dvorak or ko"),
+                }
+            },
+            "kbdlayout" => ShellResult::KbdLayout(None),
+            "mousetest" => ShellResult::MouseTest,
+            "sleep" => match parts[1].parse::<u64>().ok().and_then(|seconds| seconds.checked_mul(100)) {
+                Some(ticks) => ShellResult::Sleep(ticks),
+                None => ShellResult::Output("Usage: sleep <seconds>"),
+            },
+            "dmesg" if !parts[1].is_empty() => match parts[1].parse::<LevelFilter>() {
+                Ok(level) => ShellResult::Dmesg(level),
+                Err(_) => ShellResult::Output("Usage: dmesg [error|warn|info|debug|trace]"),
+            },
+            "dmesg" => ShellResult::Dmesg(LevelFilter::Trace),
+            "gdb" => ShellResult::Debugger,
+            "bootlog" => ShellResult::BootLog,
+            "watch" => parse_watch(parts[1], parts[2], parts[3]),
+            "int3" => ShellResult::Int3,
+            "mode" if !parts[1].is_empty() => match parse_size(parts[1]) {
+                Some(size) => ShellResult::Mode(Some(size)),
+                None => ShellResult::Output("Usage: mode [<cols>x<rows>]"),
+            },
+            "mode" => ShellResult::Mode(None),
+            "gfxtest" if !parts[1].is_empty() => match parse_size(parts[1]) {
+                Some((width, height)) => ShellResult::GfxTest(width, height),
+                None => ShellResult::Output("Usage: gfxtest [<width>x<height>]"),
+            },
+            "gfxtest" => ShellResult::GfxTest(320, 200),
+            "font" => match parts[1] {
+                "bios" => ShellResult::Font(FontStyle::Bios),
+                "bold" => ShellResult::Font(FontStyle::Bold),
+                _ => ShellResult::Output("Usage: font bios|bold"),
+            },
+            "profile" => match parts[1] {
+                "start" => ShellResult::Profile(ProfileCommand::Start),
+                "stop" => ShellResult::Profile(ProfileCommand::Stop),
+                "report" => ShellResult::Profile(ProfileCommand::Report),
+                _ => ShellResult::Output("Usage: profile start|stop|report"),
+            },
+            "" => ShellResult::Empty,
+            _ => ShellResult::Output("Unknown command. Type 'help' for commands."),
+        };
+        
+        self.clear();
+        result
+    }
+    
+    pub fn clear(&mut self) {
+        self.buffer = [0; 256];
+        self.len = 0;
+        self.cursor = 0;
+    }
+    
+    pub fn get_buffer(&self) -> &str {
+        core::str::from_utf8(&self.buffer[..self.len])
+            .unwrap_or("")
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(line: &str) -> ShellResult {
+        let mut shell = Shell::new();
+        for ch in line.chars() {
+            shell.add_char(ch);
+        }
+        shell.execute(0)
+    }
+
+    fn output(line: &str) -> &'static str {
+        match run(line) {
+            ShellResult::Output(text) => text,
+            _ => panic!("expected Output for {:?}", line),
+        }
+    }
+
+    #[test]
+    fn simple_commands() {
+        assert!(matches!(run("clear"), ShellResult::Clear));
+        assert!(matches!(run("shutdown"), ShellResult::Shutdown));
+        assert!(matches!(run("reboot"), ShellResult::Reboot));
+        assert!(matches!(run("date"), ShellResult::DateTime));
+        assert!(matches!(run("time"), ShellResult::DateTime));
+        assert!(matches!(run("cpuinfo"), ShellResult::CpuInfo));
+        assert!(matches!(run("memtest"), ShellResult::MemInfo));
+        assert!(matches!(run("sysinfo"), ShellResult::SysInfo));
+        assert!(matches!(run("mousetest"), ShellResult::MouseTest));
+        assert!(matches!(run("gdb"), ShellResult::Debugger));
+        assert!(matches!(run("bootlog"), ShellResult::BootLog));
+        assert!(matches!(run("help"), ShellResult::MultiOutput(_)));
+        assert!(output("version").starts_with("AerogelOS"));
+    }
+
+    #[test]
+    fn empty_and_unknown() {
+        assert!(matches!(run(""), ShellResult::Empty));
+        assert!(matches!(run("   "), ShellResult::Empty));
+        assert_eq!(output("foo"), "Unknown command. Type 'help' for commands.");
+        assert_eq!(output("CLEAR"), "Unknown command. Type 'help' for commands.");
+    }
+
+    #[test]
+    fn extra_whitespace() {
+        assert!(matches!(run("  clear  "), ShellResult::Clear));
+        assert!(matches!(run("bgcolor    a"), ShellResult::BgColor(0xA)));
+    }
+
+    #[test]
+    fn uptime_since_boot() {
+        let mut shell = Shell::new();
+        shell.set_boot_time(250);
+        for ch in "uptime".chars() {
+            shell.add_char(ch);
+        }
+        assert!(matches!(shell.execute(1000), ShellResult::Uptime(750)));
+    }
+
+    #[test]
+    fn bgcolor() {
+        assert!(matches!(run("bgcolor 1"), ShellResult::BgColor(1)));
+        assert!(matches!(run("bgcolor F"), ShellResult::BgColor(0xF)));
+        assert_eq!(output("bgcolor x"), "Invalid color! Use 0-F");
+        assert_eq!(output("bgcolor 10"), "Invalid color! Use 0-F");
+        assert_eq!(output("bgcolor"), "Unknown command. Type 'help' for commands.");
+    }
+
+    #[test]
+    fn bgcolor_error_clears_line() {
+        let mut shell = Shell::new();
+        for ch in "bgcolor z".chars() {
+            shell.add_char(ch);
+        }
+        shell.execute(0);
+        assert_eq!(shell.get_buffer(), "");
+    }
+
+    #[test]
+    fn print_keeps_spacing() {
+        match run("print hello   world") {
+            ShellResult::Print(buf, len) => assert_eq!(&buf[..len], b"hello   world"),
+            _ => panic!("expected Print"),
+        }
+        match run("print 안녕") {
+            ShellResult::Print(buf, len) => assert_eq!(core::str::from_utf8(&buf[..len]), Ok("안녕")),
+            _ => panic!("expected Print"),
+        }
+        assert_eq!(output("print"), "Usage: print <text>");
+    }
+
+    #[test]
+    fn sleep_seconds_to_ticks() {
+        assert!(matches!(run("sleep 3"), ShellResult::Sleep(300)));
+        assert!(matches!(run("sleep 0"), ShellResult::Sleep(0)));
+        assert_eq!(output("sleep"), "Usage: sleep <seconds>");
+        assert_eq!(output("sleep -1"), "Usage: sleep <seconds>");
+        assert_eq!(output("sleep abc"), "Usage: sleep <seconds>");
+        assert_eq!(output("sleep 200000000000000000"), "Usage: sleep <seconds>");
+        assert!(matches!(run("sleep 184467440737095516"), ShellResult::Sleep(18446744073709551600)));
+    }
+
+    #[test]
+    fn kbdlayout() {
+        assert!(matches!(run("kbdlayout"), ShellResult::KbdLayout(None)));
+        match run("kbdlayout dvorak") {
+            ShellResult::KbdLayout(Some(layout)) => assert_eq!(layout.name, "dvorak"),
+            _ => panic!("expected KbdLayout"),
+        }
+        assert_eq!(output("kbdlayout qwertz"), "Unknown layout! Use us, dvorak or ko");
+    }
+
+    #[test]
+    fn dmesg_levels() {
+        assert!(matches!(run("dmesg"), ShellResult::Dmesg(LevelFilter::Trace)));
+        assert!(matches!(run("dmesg warn"), ShellResult::Dmesg(LevelFilter::Warn)));
+        assert!(matches!(run("dmesg ERROR"), ShellResult::Dmesg(LevelFilter::Error)));
+        assert_eq!(output("dmesg loud"), "Usage: dmesg [error|warn|info|debug|trace]");
+    }
+
+    #[test]
+    fn profile_subcommands() {
+        assert!(matches!(run("profile start"), ShellResult::Profile(ProfileCommand::Start)));
+        assert!(matches!(run("profile stop"), ShellResult::Profile(ProfileCommand::Stop)));
+        assert!(matches!(run("profile report"), ShellResult::Profile(ProfileCommand::Report)));
+        assert_eq!(output("profile"), "Usage: profile start|stop|report");
+        assert_eq!(output("profile pause"), "Usage: profile start|stop|report");
+    }
+
+    #[test]
+    fn mode_sizes() {
+        assert!(matches!(run("mode"), ShellResult::Mode(None)));
+        assert!(matches!(run("mode 80x50"), ShellResult::Mode(Some((80, 50)))));
+        assert!(matches!(run("mode 90X60"), ShellResult::Mode(Some((90, 60)))));
+        assert_eq!(output("mode 80"), "Usage: mode [<cols>x<rows>]");
+        assert_eq!(output("mode ax25"), "Usage: mode [<cols>x<rows>]");
+    }
+
+    #[test]
+    fn font_styles() {
+        assert!(matches!(run("font bios"), ShellResult::Font(FontStyle::Bios)));
+        assert!(matches!(run("font bold"), ShellResult::Font(FontStyle::Bold)));
+        assert_eq!(output("font"), "Usage: font bios|bold");
+    }
+
+    #[test]
+    fn gfxtest_sizes() {
+        assert!(matches!(run("gfxtest"), ShellResult::GfxTest(320, 200)));
+        assert!(matches!(run("gfxtest 640x480"), ShellResult::GfxTest(640, 480)));
+        assert_eq!(output("gfxtest vga"), "Usage: gfxtest [<width>x<height>]");
+    }
+
+    fn watch(line: &str) -> WatchCommand {
+        match run(line) {
+            ShellResult::Watch(command) => command,
+            _ => panic!("expected Watch for {:?}", line),
+        }
+    }
+
+    #[test]
+    fn watch_commands() {
+        assert_eq!(watch("watch"), WatchCommand::List);
+        assert_eq!(watch("watch del 3"), WatchCommand::Clear(3));
+        assert_eq!(watch("watch 0x1000"),
+            WatchCommand::Set(Watch { addr: 0x1000, len: 8, kind: WatchKind::Write }));
+        assert_eq!(watch("watch 1002 rw 2"),
+            WatchCommand::Set(Watch { addr: 0x1002, len: 2, kind: WatchKind::ReadWrite }));
+        assert_eq!(watch("watch 0x201235 x"),
+            WatchCommand::Set(Watch { addr: 0x201235, len: 1, kind: WatchKind::Execute }));
+    }
+
+    #[test]
+    fn watch_errors() {
+        assert_eq!(output("watch del 4"), "Watchpoint number must be 0-3");
+        assert_eq!(output("watch nowhere"), WATCH_USAGE);
+        assert_eq!(output("watch 0x1000 r"), WATCH_USAGE);
+        assert_eq!(output("watch 0x1000 w 3"), "Length must be 1, 2, 4 or 8");
+        assert_eq!(output("watch 0x1001 w 4"), "Address must be aligned to the length");
+        assert_eq!(output("watch 0x1000 x 4"), "Execute watchpoints must have length 1");
+        assert!(matches!(run("int3"), ShellResult::Int3));
+    }
+
+    #[test]
+    fn execute_clears_buffer() {
+        let mut shell = Shell::new();
+        shell.add_char('x');
+        shell.execute(0);
+        assert_eq!(shell.get_buffer(), "");
+        assert_eq!(shell.cursor(), 0);
+    }
+
+    #[test]
+    fn cursor_editing() {
+        let mut shell = Shell::new();
+        for ch in "hllo".chars() {
+            shell.add_char(ch);
+        }
+        shell.move_home();
+        shell.move_right();
+        shell.add_char('e');
+        assert_eq!(shell.get_buffer(), "hello");
+        assert_eq!(shell.cursor(), 2);
+
+        shell.move_end();
+        shell.backspace();
+        assert_eq!(shell.get_buffer(), "hell");
+
+        shell.move_home();
+        shell.delete();
+        assert_eq!(shell.get_buffer(), "ell");
+        shell.move_end();
+        shell.delete(); // 끝에서는 아무 일도 없음
+        shell.move_right();
+        assert_eq!(shell.get_buffer(), "ell");
+        assert_eq!(shell.cursor(), 3);
+    }
+
+    #[test]
+    fn utf8_editing() {
+        let mut shell = Shell::new();
+        for ch in "a한b".chars() {
+            shell.add_char(ch);
+        }
+        shell.move_left();
+        assert_eq!(shell.cursor(), 4);
+        shell.move_left();
+        assert_eq!(shell.cursor(), 1);
+        shell.delete();
+        assert_eq!(shell.get_buffer(), "ab");
+
+        shell.move_end();
+        shell.add_char('글');
+        shell.backspace();
+        assert_eq!(shell.get_buffer(), "ab");
+    }
+
+    #[test]
+    fn line_length_limit() {
+        let mut shell = Shell::new();
+        for _ in 0..300 {
+            shell.add_char('a');
+        }
+        assert_eq!(shell.get_buffer().len(), 255);
+        // 멀티바이트 문자는 통째로 들어가거나 안 들어감
+        shell.backspace();
+        shell.add_char('한');
+        assert_eq!(shell.get_buffer().len(), 254);
+    }
+}
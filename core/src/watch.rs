@@ -0,0 +1,120 @@
+// core/src/watch.rs - 하드웨어 워치포인트 설정값 검사 (DR0-DR3/DR7 규칙)
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchKind {
+    Write,     // 쓰기
+    ReadWrite, // 읽기/쓰기 (명령어 인출 제외)
+    Execute,   // 명령 실행
+}
+
+impl WatchKind {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "w" => Some(WatchKind::Write),
+            "rw" => Some(WatchKind::ReadWrite),
+            "x" => Some(WatchKind::Execute),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WatchKind::Write => "w",
+            WatchKind::ReadWrite => "rw",
+            WatchKind::Execute => "x",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchError {
+    BadLength,     // 1, 2, 4, 8만 가능
+    Unaligned,     // 주소가 길이에 맞게 정렬되어야 함
+    ExecuteLength, // 실행 워치포인트는 길이 1
+    NonCanonical,
+}
+
+impl WatchError {
+    pub fn message(self) -> &'static str {
+        match self {
+            WatchError::BadLength => "Length must be 1, 2, 4 or 8",
+            WatchError::Unaligned => "Address must be aligned to the length",
+            WatchError::ExecuteLength => "Execute watchpoints must have length 1",
+            WatchError::NonCanonical => "Address is not canonical",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Watch {
+    pub addr: u64,
+    pub len: u8,
+    pub kind: WatchKind,
+}
+
+impl Watch {
+    pub fn new(addr: u64, kind: WatchKind, len: u8) -> Result<Self, WatchError> {
+        if !matches!(len, 1 | 2 | 4 | 8) {
+            return Err(WatchError::BadLength);
+        }
+        if kind == WatchKind::Execute && len != 1 {
+            return Err(WatchError::ExecuteLength);
+        }
+        if !addr.is_multiple_of(len as u64) {
+            return Err(WatchError::Unaligned);
+        }
+        let top = addr >> 47;
+        if top != 0 && top != 0x1FFFF {
+            return Err(WatchError::NonCanonical);
+        }
+        Ok(Watch { addr, len, kind })
+    }
+}
+
+// "0x1234" 또는 "1234" (16진수)
+pub fn parse_addr(s: &str) -> Option<u64> {
+    let digits = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")).unwrap_or(s);
+    u64::from_str_radix(digits, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kinds_round_trip() {
+        for kind in [WatchKind::Write, WatchKind::ReadWrite, WatchKind::Execute] {
+            assert_eq!(WatchKind::parse(kind.as_str()), Some(kind));
+        }
+        assert_eq!(WatchKind::parse("r"), None);
+    }
+
+    #[test]
+    fn valid_watches() {
+        assert!(Watch::new(0x1000, WatchKind::Write, 8).is_ok());
+        assert!(Watch::new(0x1002, WatchKind::ReadWrite, 2).is_ok());
+        assert!(Watch::new(0x1003, WatchKind::Write, 1).is_ok());
+        assert!(Watch::new(0x20_1235, WatchKind::Execute, 1).is_ok());
+        assert!(Watch::new(0xFFFF_8000_0000_0000, WatchKind::Write, 8).is_ok());
+    }
+
+    #[test]
+    fn invalid_watches() {
+        assert_eq!(Watch::new(0x1000, WatchKind::Write, 3), Err(WatchError::BadLength));
+        assert_eq!(Watch::new(0x1000, WatchKind::Write, 16), Err(WatchError::BadLength));
+        assert_eq!(Watch::new(0x1004, WatchKind::Write, 8), Err(WatchError::Unaligned));
+        assert_eq!(Watch::new(0x1001, WatchKind::ReadWrite, 2), Err(WatchError::Unaligned));
+        assert_eq!(Watch::new(0x1000, WatchKind::Execute, 4), Err(WatchError::ExecuteLength));
+        assert_eq!(Watch::new(0x0000_8000_0000_0000, WatchKind::Write, 8), Err(WatchError::NonCanonical));
+    }
+
+    #[test]
+    fn addresses() {
+        assert_eq!(parse_addr("0x1f"), Some(0x1F));
+        assert_eq!(parse_addr("0XAB"), Some(0xAB));
+        assert_eq!(parse_addr("deadbeef"), Some(0xDEADBEEF));
+        assert_eq!(parse_addr("0x"), None);
+        assert_eq!(parse_addr("xyz"), None);
+        assert_eq!(parse_addr("10000000000000000"), None);
+    }
+}
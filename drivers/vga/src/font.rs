@@ -0,0 +1,78 @@
+// drivers/vga/src/font.rs - 텍스트 모드 글꼴 (평면 2) 읽기/쓰기
+use crate::regs::{gc_read, gc_write, seq_read, seq_write, GC_MISC, GC_MODE, GC_READ_MAP, SEQ_MAP_MASK, SEQ_MEMORY_MODE};
+
+pub const GLYPHS: usize = 256;
+const GLYPH_STRIDE: usize = 32; // 평면 2에서 글자 하나가 차지하는 바이트 (높이와 상관없음)
+const FONT_MEMORY: usize = 0xA0000;
+
+// 글꼴 평면에 접근하는 동안 바꾼 레지스터
+struct PlaneAccess {
+    map_mask: u8,
+    memory_mode: u8,
+    read_map: u8,
+    mode: u8,
+    misc: u8,
+}
+
+impl PlaneAccess {
+    // 평면 2만 A0000에 순차 주소로 보이게 함 (홀짝 주소 끔)
+    fn begin() -> Self {
+        let saved = PlaneAccess {
+            map_mask: seq_read(SEQ_MAP_MASK),
+            memory_mode: seq_read(SEQ_MEMORY_MODE),
+            read_map: gc_read(GC_READ_MAP),
+            mode: gc_read(GC_MODE),
+            misc: gc_read(GC_MISC),
+        };
+        seq_write(SEQ_MAP_MASK, 0x04);
+        seq_write(SEQ_MEMORY_MODE, saved.memory_mode | 0x04);
+        gc_write(GC_READ_MAP, 0x02);
+        gc_write(GC_MODE, saved.mode & !0x10);
+        gc_write(GC_MISC, 0x04); // A0000-AFFFF, 텍스트 모드 유지
+        saved
+    }
+
+    fn end(self) {
+        seq_write(SEQ_MAP_MASK, self.map_mask);
+        seq_write(SEQ_MEMORY_MODE, self.memory_mode);
+        gc_write(GC_READ_MAP, self.read_map);
+        gc_write(GC_MODE, self.mode);
+        gc_write(GC_MISC, self.misc);
+    }
+}
+
+fn glyph_row(glyph: usize, row: usize) -> *mut u8 {
+    (FONT_MEMORY + glyph * GLYPH_STRIDE + row) as *mut u8
+}
+
+// 글자당 height바이트(한 바이트가 한 줄, 최상위 비트가 왼쪽)인 글꼴을 올림
+// glyphs가 256글자보다 짧으면 있는 만큼만. height가 0이면 아무것도 하지 않음
+pub fn load_font(glyphs: &[u8], height: usize) {
+    if height == 0 {
+        return;
+    }
+    let height = height.min(GLYPH_STRIDE);
+    let access = PlaneAccess::begin();
+    for (glyph, rows) in glyphs.chunks(height).take(GLYPHS).enumerate() {
+        for row in 0..GLYPH_STRIDE {
+            let bits = rows.get(row).copied().unwrap_or(0);
+            unsafe { glyph_row(glyph, row).write_volatile(bits) }
+        }
+    }
+    access.end();
+}
+
+// 현재 글꼴을 글자당 height바이트로 읽음 (buf 길이는 256 * height 이상)
+pub fn read_font(buf: &mut [u8], height: usize) {
+    if height == 0 {
+        return;
+    }
+    let height = height.min(GLYPH_STRIDE);
+    let access = PlaneAccess::begin();
+    for (glyph, rows) in buf.chunks_mut(height).take(GLYPHS).enumerate() {
+        for (row, bits) in rows.iter_mut().enumerate() {
+            *bits = unsafe { glyph_row(glyph, row).read_volatile() };
+        }
+    }
+    access.end();
+}
@@ -0,0 +1,256 @@
+// drivers/vga/src/graphics.rs - VGA 그래픽 모드 (320x200 256색, 640x480 16색)와 그리기
+use kernel_core::raster::{clip_rect, LinePoints};
+use crate::palette;
+use crate::regs::{ac_read, gc_write, seq_write, ModeRegs, GC_BIT_MASK, GC_MODE, GC_READ_MAP, SEQ_MAP_MASK};
+
+const GRAPHICS_MEMORY: usize = 0xA0000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsMode {
+    Mode320x200x256, // 모드 13h: 한 바이트가 한 픽셀
+    Mode640x480x16,  // 모드 12h: 평면 4개, 한 바이트가 가로 8픽셀
+}
+
+impl GraphicsMode {
+    pub const ALL: [GraphicsMode; 2] = [GraphicsMode::Mode320x200x256, GraphicsMode::Mode640x480x16];
+
+    pub fn width(self) -> usize {
+        match self {
+            GraphicsMode::Mode320x200x256 => 320,
+            GraphicsMode::Mode640x480x16 => 640,
+        }
+    }
+
+    pub fn height(self) -> usize {
+        match self {
+            GraphicsMode::Mode320x200x256 => 200,
+            GraphicsMode::Mode640x480x16 => 480,
+        }
+    }
+
+    pub fn colors(self) -> usize {
+        match self {
+            GraphicsMode::Mode320x200x256 => 256,
+            GraphicsMode::Mode640x480x16 => 16,
+        }
+    }
+
+    pub fn from_size(width: usize, height: usize) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.width() == width && mode.height() == height)
+    }
+
+    fn regs(self) -> ModeRegs {
+        match self {
+            GraphicsMode::Mode320x200x256 => ModeRegs {
+                misc: 0x63,
+                seq: [0x03, 0x01, 0x0F, 0x00, 0x0E],
+                crtc: [
+                    0x5F, 0x4F, 0x50, 0x82, 0x54, 0x80, 0xBF, 0x1F,
+                    0x00, 0x41, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x9C, 0x0E, 0x8F, 0x28, 0x40, 0x96, 0xB9, 0xA3,
+                    0xFF,
+                ],
+                gc: [0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0x05, 0x0F, 0xFF],
+                ac: [
+                    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+                    0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+                    0x41, 0x00, 0x0F, 0x00, 0x00,
+                ],
+            },
+            GraphicsMode::Mode640x480x16 => ModeRegs {
+                misc: 0xE3,
+                seq: [0x03, 0x01, 0x0F, 0x00, 0x06],
+                crtc: [
+                    0x5F, 0x4F, 0x50, 0x82, 0x54, 0x80, 0x0B, 0x3E,
+                    0x00, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0xEA, 0x0C, 0xDF, 0x28, 0x00, 0xE7, 0x04, 0xE3,
+                    0xFF,
+                ],
+                gc: [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05, 0x0F, 0xFF],
+                // 텍스트 모드와 같은 16색 (DAC 0-5, 0x14, 7, 0x38-0x3F)
+                ac: [
+                    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x14, 0x07,
+                    0x38, 0x39, 0x3A, 0x3B, 0x3C, 0x3D, 0x3E, 0x3F,
+                    0x01, 0x00, 0x0F, 0x00, 0x00,
+                ],
+            },
+        }
+    }
+}
+
+// 그래픽 모드로 바꾸고 화면을 0번 색으로 지움
+// 그래픽 모드는 글꼴 평면과 텍스트 버퍼를 덮어쓰므로 텍스트 모드로 돌아갈 때 둘 다 다시 올려야 함
+pub fn set_graphics_mode(mode: GraphicsMode) -> Canvas {
+    mode.regs().write();
+    // 256색 모드는 0-15번이 DAC 0-15를 그대로 쓰는데, 텍스트 팔레트의 6번과 8-15번은 DAC 0x14, 0x38-0x3F에 있음
+    if mode == GraphicsMode::Mode320x200x256 {
+        palette::set_palette(0, &palette::STANDARD);
+    }
+    let canvas = Canvas { mode };
+    canvas.clear(0);
+    canvas
+}
+
+// 현재 그래픽 모드의 화면. 좌표가 화면 밖인 부분은 그리지 않음
+pub struct Canvas {
+    mode: GraphicsMode,
+}
+
+impl Canvas {
+    pub fn mode(&self) -> GraphicsMode {
+        self.mode
+    }
+
+    // (너비, 높이)
+    pub fn size(&self) -> (usize, usize) {
+        (self.mode.width(), self.mode.height())
+    }
+
+    // 색 번호의 RGB (0-63). 16색 모드는 속성 컨트롤러가 고르는 DAC 번호를 바꿈
+    pub fn set_palette(&self, color: u8, r: u8, g: u8, b: u8) {
+        palette::set_color(self.dac_index(color), r, g, b);
+    }
+
+    pub fn palette(&self, color: u8) -> (u8, u8, u8) {
+        palette::color(self.dac_index(color))
+    }
+
+    fn dac_index(&self, color: u8) -> u8 {
+        match self.mode {
+            GraphicsMode::Mode320x200x256 => color,
+            GraphicsMode::Mode640x480x16 => ac_read(color & 0x0F) & 0x3F,
+        }
+    }
+
+    pub fn put_pixel(&self, x: i32, y: i32, color: u8) {
+        if let Some((x, y, _, _)) = clip_rect(x, y, 1, 1, self.mode.width(), self.mode.height()) {
+            self.span(x, y, 1, color);
+        }
+    }
+
+    // 화면 밖이면 0
+    pub fn pixel(&self, x: usize, y: usize) -> u8 {
+        if x >= self.mode.width() || y >= self.mode.height() {
+            return 0;
+        }
+        match self.mode {
+            GraphicsMode::Mode320x200x256 => unsafe { linear(x, y).read_volatile() },
+            GraphicsMode::Mode640x480x16 => {
+                let (byte, bit) = planar(x, y);
+                let mut color = 0;
+                for plane in 0..4 {
+                    gc_write(GC_READ_MAP, plane);
+                    if unsafe { byte.read_volatile() } & bit != 0 {
+                        color |= 1 << plane;
+                    }
+                }
+                color
+            },
+        }
+    }
+
+    pub fn line(&self, x0: i32, y0: i32, x1: i32, y1: i32, color: u8) {
+        for (x, y) in LinePoints::new(x0, y0, x1, y1) {
+            self.put_pixel(x, y, color);
+        }
+    }
+
+    // 테두리만
+    pub fn rect(&self, x: i32, y: i32, w: usize, h: usize, color: u8) {
+        if w == 0 || h == 0 {
+            return;
+        }
+        let (right, bottom) = (x + w as i32 - 1, y + h as i32 - 1);
+        self.fill_rect(x, y, w, 1, color);
+        self.fill_rect(x, bottom, w, 1, color);
+        self.line(x, y, x, bottom, color);
+        self.line(right, y, right, bottom, color);
+    }
+
+    pub fn fill_rect(&self, x: i32, y: i32, w: usize, h: usize, color: u8) {
+        if let Some((x, y, w, h)) = clip_rect(x, y, w, h, self.mode.width(), self.mode.height()) {
+            for row in y..y + h {
+                self.span(x, row, w, color);
+            }
+        }
+    }
+
+    pub fn clear(&self, color: u8) {
+        self.fill_rect(0, 0, self.mode.width(), self.mode.height(), color);
+    }
+
+    // w x h 픽셀(한 바이트에 색 하나, 줄 단위)을 (x, y)에 복사
+    pub fn blit(&self, x: i32, y: i32, w: usize, h: usize, pixels: &[u8]) {
+        let Some((cx, cy, cw, ch)) = clip_rect(x, y, w, h, self.mode.width(), self.mode.height()) else {
+            return;
+        };
+        // 잘려 나간 만큼 원본에서도 건너뜀
+        let (sx, sy) = ((cx as i64 - x as i64) as usize, (cy as i64 - y as i64) as usize);
+        for row in 0..ch {
+            let start = (sy + row) * w + sx;
+            let Some(src) = pixels.get(start..start + cw) else {
+                return;
+            };
+            match self.mode {
+                GraphicsMode::Mode320x200x256 => {
+                    for (i, &color) in src.iter().enumerate() {
+                        unsafe { linear(cx + i, cy + row).write_volatile(color) }
+                    }
+                },
+                GraphicsMode::Mode640x480x16 => with_write_mode2(|| {
+                    for (i, &color) in src.iter().enumerate() {
+                        planar_write(cx + i, cy + row, 1, color);
+                    }
+                }),
+            }
+        }
+    }
+
+    // 가로 한 줄 (이미 화면 안으로 자른 좌표)
+    fn span(&self, x: usize, y: usize, w: usize, color: u8) {
+        match self.mode {
+            GraphicsMode::Mode320x200x256 => {
+                for i in 0..w {
+                    unsafe { linear(x + i, y).write_volatile(color) }
+                }
+            },
+            GraphicsMode::Mode640x480x16 => with_write_mode2(|| planar_write(x, y, w, color)),
+        }
+    }
+}
+
+fn linear(x: usize, y: usize) -> *mut u8 {
+    (GRAPHICS_MEMORY + y * 320 + x) as *mut u8
+}
+
+// 픽셀이 있는 바이트와 그 안의 비트
+fn planar(x: usize, y: usize) -> (*mut u8, u8) {
+    ((GRAPHICS_MEMORY + y * 80 + x / 8) as *mut u8, 0x80 >> (x % 8))
+}
+
+// 쓰기 모드 2: 쓴 값의 아래 4비트가 네 평면의 색, 비트 마스크로 고른 픽셀만 바뀜
+fn with_write_mode2(f: impl FnOnce()) {
+    seq_write(SEQ_MAP_MASK, 0x0F);
+    gc_write(GC_MODE, 0x02);
+    f();
+    gc_write(GC_BIT_MASK, 0xFF);
+    gc_write(GC_MODE, 0x00);
+}
+
+// x부터 w픽셀을 바이트 단위로 (바이트마다 래치를 읽어 나머지 픽셀 유지)
+fn planar_write(x: usize, y: usize, w: usize, color: u8) {
+    let end = x + w;
+    let mut x = x;
+    while x < end {
+        let (byte, _) = planar(x, y);
+        let next = (x / 8 + 1) * 8;
+        let stop = next.min(end);
+        let mask = (0xFFu8 >> (x % 8)) & !((0xFFu16 >> (stop - x / 8 * 8)) as u8);
+        gc_write(GC_BIT_MASK, mask);
+        unsafe {
+            byte.read_volatile();
+            byte.write_volatile(color);
+        }
+        x = stop;
+    }
+}
@@ -1,8 +1,17 @@
-// ../drivers/src/lib.rs
+// drivers/vga/src/lib.rs - VGA 텍스트 Writer (스크롤백, ANSI, 하드웨어 커서)와 모드/글꼴/그래픽 모듈
 #![no_std]
 #![feature(abi_x86_interrupt)]
 use core::fmt;
-use volatile::Volatile;
+use core::ops::Range;
+use kernel_core::ansi::{self, Action, Erase, Parser};
+use kernel_core::cp437;
+use regs::{crtc_read, crtc_write, CRTC_MAX_SCAN_LINE};
+
+pub mod font;
+pub mod graphics;
+pub mod mode;
+pub mod palette;
+pub mod regs;
 
 #[allow(dead_code)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -31,7 +40,7 @@ pub enum Color {
 struct ColorCode(u8);
 
 impl ColorCode {
-    fn new(foreground: Color, background: Color) -> ColorCode {
+    const fn new(foreground: Color, background: Color) -> ColorCode {
         ColorCode((background as u8) << 4 | (foreground as u8))
     }
 }
@@ -43,131 +52,436 @@ struct ScreenChar {
     color_code: ColorCode,
 }
 
-const BUFFER_HEIGHT: usize = 25;
-const BUFFER_WIDTH: usize = 80;
+// 지원하는 가장 큰 텍스트 모드 (실제 크기는 Writer::size)
+pub const MAX_WIDTH: usize = 90;
+pub const MAX_HEIGHT: usize = 60;
+const TAB_WIDTH: usize = 8;
+pub const SCROLLBACK: usize = 500; // 화면 위로 밀려난 줄을 보관하는 수
+const VGA_BUFFER: usize = 0xb8000;
 
-#[repr(transparent)]
-struct Buffer {
-    chars: [[Volatile<ScreenChar>; BUFFER_WIDTH]; BUFFER_HEIGHT],
+// CRTC 커서 레지스터
+const CRTC_CURSOR_START: u8 = 0x0A;
+const CRTC_CURSOR_END: u8 = 0x0B;
+const CRTC_CURSOR_HIGH: u8 = 0x0E;
+const CRTC_CURSOR_LOW: u8 = 0x0F;
+const CURSOR_DISABLE: u8 = 0x20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorShape {
+    Underline, // 글자 칸 아래 두 줄
+    Block,     // 글자 칸 전체
 }
 
+// VGA 버퍼는 현재 모드의 너비로 줄이 이어짐
+fn vga_write(index: usize, character: ScreenChar) {
+    unsafe { (VGA_BUFFER as *mut ScreenChar).add(index).write_volatile(character) }
+}
+
+type Line = [ScreenChar; MAX_WIDTH];
+
+const BLANK: ScreenChar = ScreenChar {
+    ascii_character: b' ',
+    color_code: ColorCode::new(Color::White, Color::Black),
+};
+
+// VGA 텍스트 화면 전체를 쓰는 출력기 (커서 위치부터 쓰고 끝에 닿으면 스크롤)
+// ANSI 이스케이프 시퀀스로 색, 커서 위치, 지우기를 제어할 수 있음
+// 화면 내용은 메모리에 두고 VGA 버퍼에는 보이는 부분만 복사 (스크롤백을 보는 중에도 출력 유지)
+// 여러 Writer 중 활성화된 하나만 VGA 버퍼와 하드웨어 커서를 씀 (가상 터미널)
 pub struct Writer {
     column_position: usize,
+    row_position: usize,
     color_code: ColorCode,
-    buffer: &'static mut Buffer,
+    default_color: ColorCode, // SGR 0 (리셋)
+    saved_position: (usize, usize),
+    parser: Parser,
+    cursor_visible: bool,
+    cursor_shape: CursorShape,
+    active: bool,
+    width: usize,
+    height: usize,
+    screen: [Line; MAX_HEIGHT], // 앞쪽 height줄, 각 줄의 앞쪽 width칸만 사용
+    history: [Line; SCROLLBACK], // 링 버퍼
+    history_next: usize,
+    history_len: usize,
+    view_offset: usize, // 스크롤백을 보고 있는 줄 수 (0이면 맨 아래)
 }
 
 impl Writer {
+    pub const fn new() -> Self {
+        Writer {
+            column_position: 0,
+            row_position: 0,
+            color_code: ColorCode::new(Color::White, Color::Black),
+            default_color: ColorCode::new(Color::White, Color::Black),
+            saved_position: (0, 0),
+            parser: Parser::new(),
+            cursor_visible: true, // BIOS가 켜 둔 상태
+            cursor_shape: CursorShape::Underline,
+            active: false,
+            width: 80,
+            height: 25,
+            screen: [[BLANK; MAX_WIDTH]; MAX_HEIGHT],
+            history: [[BLANK; MAX_WIDTH]; SCROLLBACK],
+            history_next: 0,
+            history_len: 0,
+            view_offset: 0,
+        }
+    }
+
+    // 처음부터 화면에 보이는 Writer
+    pub const fn activated(mut self) -> Self {
+        self.active = true;
+        self
+    }
+
+    // 이 Writer를 화면에 표시 (내용과 커서를 VGA에 복원)
+    pub fn activate(&mut self) {
+        self.active = true;
+        self.render();
+        self.apply_cursor_shape();
+        self.update_cursor();
+    }
+
+    // 화면에서 내림 (내용은 메모리에 계속 기록)
+    pub fn deactivate(&mut self) {
+        self.active = false;
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    // (열, 행)
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    // 텍스트 모드가 바뀌었을 때 화면 크기를 맞춤
+    // 커서 줄이 화면 밖이면 위쪽 줄을 스크롤백으로 밀어 올리고, 새 크기 밖의 칸은 비움
+    pub fn resize(&mut self, width: usize, height: usize) {
+        let width = width.clamp(1, MAX_WIDTH);
+        let height = height.clamp(1, MAX_HEIGHT);
+        while self.row_position >= height {
+            self.shift_up();
+            self.row_position -= 1;
+        }
+        let blank = ScreenChar { ascii_character: b' ', color_code: self.color_code };
+        for (row, line) in self.screen.iter_mut().enumerate() {
+            let start = if row < height { width } else { 0 };
+            line[start..].fill(blank);
+        }
+
+        self.width = width;
+        self.height = height;
+        self.column_position = self.column_position.min(width);
+        self.saved_position = (self.saved_position.0.min(width - 1), self.saved_position.1.min(height - 1));
+        self.view_offset = 0;
+        self.render();
+        self.apply_cursor_shape();
+        self.update_cursor();
+    }
+
     pub fn write_byte(&mut self, byte: u8) {
+        self.scroll_to_bottom();
+        self.put_byte(byte);
+        self.update_cursor();
+    }
+
+    fn put_byte(&mut self, byte: u8) {
         match byte {
             b'\n' => self.new_line(),
-            byte => {
-                if self.column_position >= BUFFER_WIDTH {
-                    self.new_line();
+            b'\r' => self.column_position = 0,
+            b'\t' => {
+                // 다음 탭 위치까지 공백 (줄 끝을 넘지 않음)
+                let next = (self.column_position / TAB_WIDTH + 1) * TAB_WIDTH;
+                while self.column_position < next.min(self.width) {
+                    self.put_glyph(b' ');
                 }
+            },
+            0x08 => self.column_position = self.column_position.saturating_sub(1),
+            byte => self.put_glyph(byte),
+        }
+    }
 
-                let row = BUFFER_HEIGHT - 1;
-                let col = self.column_position;
-
-                let color_code = self.color_code;
-                self.buffer.chars[row][col].write(ScreenChar {
-                    ascii_character: byte,
-                    color_code,
-                });
-                self.column_position += 1;
-            }
+    // CP437 글자 하나 (제어 문자 자리의 기호도 그대로 표시)
+    fn put_glyph(&mut self, byte: u8) {
+        if self.column_position >= self.width {
+            self.new_line();
         }
+
+        let row = self.row_position;
+        let col = self.column_position;
+
+        let color_code = self.color_code;
+        self.set(row, col, ScreenChar {
+            ascii_character: byte,
+            color_code,
+        });
+        self.column_position += 1;
     }
 
     pub fn write_string(&mut self, s: &str) {
-        for byte in s.bytes() {
-            match byte {
-                0x20..=0x7e | b'\n' => self.write_byte(byte),
-                _ => self.write_byte(0xfe), // ■ 문자
+        self.scroll_to_bottom();
+        for ch in s.chars() {
+            match self.parser.feed(ch) {
+                Some(Action::Print(ch)) => match ch {
+                    '\n' | '\r' | '\t' | '\x08' => self.put_byte(ch as u8),
+                    _ => self.put_glyph(cp437::encode(ch)), // 표에 없으면 ■
+                },
+                Some(action) => self.apply(action),
+                None => {},
             }
         }
+        self.update_cursor();
+    }
+
+    fn apply(&mut self, action: Action) {
+        // 줄 끝을 넘은 상태면 마지막 칸 기준
+        let col = self.column_position.min(self.width - 1);
+        let row = self.row_position;
+        match action {
+            Action::Print(_) => {},
+            Action::Sgr(params) => {
+                self.color_code = ColorCode(ansi::apply_sgr(self.color_code.0, self.default_color.0, &params));
+            },
+            Action::CursorUp(n) => self.row_position = row.saturating_sub(n),
+            Action::CursorDown(n) => self.row_position = (row + n).min(self.height - 1),
+            Action::CursorForward(n) => self.column_position = (col + n).min(self.width - 1),
+            Action::CursorBack(n) => self.column_position = col.saturating_sub(n),
+            Action::CursorColumn(c) => self.column_position = c.min(self.width - 1),
+            Action::CursorTo { col, row } => {
+                self.column_position = col.min(self.width - 1);
+                self.row_position = row.min(self.height - 1);
+            },
+            Action::EraseLine(erase) => match erase {
+                Erase::ToEnd => self.blank(row, col..self.width),
+                Erase::ToStart => self.blank(row, 0..col + 1),
+                Erase::All => self.blank(row, 0..self.width),
+            },
+            Action::EraseScreen(erase) => {
+                let (rows, cols) = match erase {
+                    Erase::ToEnd => (row + 1..self.height, col..self.width),
+                    Erase::ToStart => (0..row, 0..col + 1),
+                    Erase::All => (0..self.height, 0..0),
+                };
+                self.blank(row, cols);
+                for r in rows {
+                    self.blank(r, 0..self.width);
+                }
+            },
+            Action::SaveCursor => self.saved_position = (self.column_position, self.row_position),
+            Action::RestoreCursor => (self.column_position, self.row_position) = self.saved_position,
+            Action::ShowCursor(true) if !self.cursor_visible => self.enable_cursor(CursorShape::Underline),
+            Action::ShowCursor(false) if self.cursor_visible => self.disable_cursor(),
+            Action::ShowCursor(_) => {},
+        }
     }
 
     fn new_line(&mut self) {
-        for row in 1..BUFFER_HEIGHT {
-            for col in 0..BUFFER_WIDTH {
-                let character = self.buffer.chars[row][col].read();
-                self.buffer.chars[row - 1][col].write(character);
-            }
+        if self.row_position + 1 < self.height {
+            self.row_position += 1;
+        } else {
+            self.scroll_up();
         }
-        self.clear_row(BUFFER_HEIGHT - 1);
         self.column_position = 0;
     }
 
-    fn clear_row(&mut self, row: usize) {
+    // 한 줄씩 위로 올리고 마지막 줄을 비움 (커서는 그대로). 맨 윗줄은 스크롤백으로
+    pub fn scroll_up(&mut self) {
+        self.shift_up();
+        self.render();
+    }
+
+    fn shift_up(&mut self) {
+        self.history[self.history_next] = self.screen[0];
+        self.history_next = (self.history_next + 1) % SCROLLBACK;
+        self.history_len = (self.history_len + 1).min(SCROLLBACK);
+
+        self.screen.copy_within(1..self.height, 0);
+        self.screen[self.height - 1] = [ScreenChar { ascii_character: b' ', color_code: self.color_code }; MAX_WIDTH];
+    }
+
+    // 칸 하나를 쓰고 맨 아래를 보고 있으면 화면에도 반영
+    fn set(&mut self, row: usize, col: usize, character: ScreenChar) {
+        self.screen[row][col] = character;
+        if self.active && self.view_offset == 0 {
+            vga_write(row * self.width + col, character);
+        }
+    }
+
+    // 보이는 줄 전체를 VGA 버퍼로 복사
+    fn render(&mut self) {
+        if !self.active {
+            return;
+        }
+        for row in 0..self.height {
+            let line = match (row + self.history_len).checked_sub(self.view_offset) {
+                Some(index) if index >= self.history_len => &self.screen[index - self.history_len],
+                _ => {
+                    // 스크롤백: 가장 오래된 줄부터 history_len - view_offset + row 번째
+                    let back = self.view_offset - row;
+                    &self.history[(self.history_next + SCROLLBACK - back) % SCROLLBACK]
+                },
+            };
+            for (col, &character) in line[..self.width].iter().enumerate() {
+                vga_write(row * self.width + col, character);
+            }
+        }
+    }
+
+    // lines만큼 과거(양수) 또는 최근(음수) 쪽으로 화면 이동
+    pub fn scroll_view(&mut self, lines: isize) {
+        let offset = self.view_offset.saturating_add_signed(lines).min(self.history_len);
+        if offset != self.view_offset {
+            self.view_offset = offset;
+            self.render();
+            self.update_cursor();
+        }
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll_view(-(self.view_offset as isize));
+    }
+
+    pub fn is_scrolled_back(&self) -> bool {
+        self.view_offset > 0
+    }
+
+    pub fn clear_row(&mut self, row: usize) {
+        self.blank(row, 0..self.width);
+    }
+
+    // 현재 색의 공백으로 채움
+    fn blank(&mut self, row: usize, cols: Range<usize>) {
         let blank = ScreenChar {
             ascii_character: b' ',
             color_code: self.color_code,
         };
-        for col in 0..BUFFER_WIDTH {
-            self.buffer.chars[row][col].write(blank);
+        for col in cols {
+            self.set(row, col, blank);
         }
     }
 
     pub fn clear_screen(&mut self) {
-        for row in 0..BUFFER_HEIGHT {
+        for row in 0..self.height {
             self.clear_row(row);
         }
         self.column_position = 0;
+        self.row_position = 0;
+        self.update_cursor();
     }
 
     pub fn set_color(&mut self, foreground: Color, background: Color) {
         self.color_code = ColorCode::new(foreground, background);
     }
-}
 
-impl fmt::Write for Writer {
-    fn write_str(&mut self, s: &str) -> fmt::Result {
-        self.write_string(s);
-        Ok(())
+    // 속성 바이트 (배경 << 4 | 전경)
+    pub fn color_code(&self) -> u8 {
+        self.color_code.0
     }
-}
 
-use spin::Mutex;
-use lazy_static::lazy_static;
+    pub fn set_color_code(&mut self, attr: u8) {
+        self.color_code = ColorCode(attr);
+    }
 
-lazy_static! {
-    pub static ref WRITER: Mutex<Writer> = Mutex::new(Writer {
-        column_position: 0,
-        color_code: ColorCode::new(Color::Yellow, Color::Black),
-        buffer: unsafe { &mut *(0xb8000 as *mut Buffer) },
-    });
-}
+    // 화면 전체의 배경색을 바꾸고 이후 출력에도 적용
+    pub fn set_background(&mut self, background: u8) {
+        for line in self.screen.iter_mut().chain(self.history.iter_mut()) {
+            for character in line.iter_mut() {
+                character.color_code = ColorCode((background << 4) | (character.color_code.0 & 0x0F));
+            }
+        }
+        self.render();
+        self.color_code = ColorCode((background << 4) | (self.color_code.0 & 0x0F));
+        self.default_color = ColorCode((background << 4) | (self.default_color.0 & 0x0F));
+    }
 
-#[macro_export]
-macro_rules! print {
-    ($($arg:tt)*) => ($crate::_print(format_args!($($arg)*)));
-}
+    pub fn position(&self) -> (usize, usize) {
+        (self.column_position, self.row_position)
+    }
 
-#[macro_export]
-macro_rules! println {
-    () => ($crate::print!("\n"));
-    ($($arg:tt)*) => ({
-        $crate::print!($($arg)*);
-        $crate::print!("\n");
-    });
-}
+    // 화면 밖이면 가장자리로 맞춤
+    pub fn set_position(&mut self, col: usize, row: usize) {
+        self.column_position = col.min(self.width);
+        self.row_position = row.min(self.height - 1);
+        self.update_cursor();
+    }
 
-#[doc(hidden)]
-pub fn _print(args: fmt::Arguments) {
-    use core::fmt::Write;
-    WRITER.lock().write_fmt(args).unwrap();
+    // 하드웨어 커서를 쓰기 위치로 (줄 끝을 넘었으면 마지막 칸)
+    fn update_cursor(&self) {
+        if !self.active || !self.cursor_visible {
+            return;
+        }
+        let col = self.column_position.min(self.width - 1);
+        // 스크롤백을 보는 중에는 화면 아래로 밀려난 만큼 옮기고, 벗어나면 화면 밖으로
+        let row = self.row_position + self.view_offset;
+        let pos = if row < self.height { row * self.width + col } else { self.width * self.height } as u16;
+        crtc_write(CRTC_CURSOR_HIGH, (pos >> 8) as u8);
+        crtc_write(CRTC_CURSOR_LOW, pos as u8);
+    }
+
+    pub fn enable_cursor(&mut self, shape: CursorShape) {
+        self.cursor_visible = true;
+        self.set_cursor_shape(shape);
+        self.update_cursor();
+    }
+
+    pub fn disable_cursor(&mut self) {
+        self.cursor_visible = false;
+        self.apply_cursor_shape();
+    }
+
+    pub fn cursor_visible(&self) -> bool {
+        self.cursor_visible
+    }
+
+    pub fn set_cursor_shape(&mut self, shape: CursorShape) {
+        self.cursor_shape = shape;
+        self.apply_cursor_shape();
+    }
+
+    // 모양은 글자 높이(최대 스캔 줄)에 맞춤. 꺼져 있으면 꺼진 채로 둠
+    fn apply_cursor_shape(&self) {
+        if !self.active {
+            return;
+        }
+        let last = crtc_read(CRTC_MAX_SCAN_LINE) & 0x1F;
+        let first = match self.cursor_shape {
+            CursorShape::Underline => last.saturating_sub(1),
+            CursorShape::Block => 0,
+        };
+        let disable = if self.cursor_visible { 0 } else { CURSOR_DISABLE };
+        // 시작/끝 레지스터의 상위 비트는 예약이므로 유지
+        let start = crtc_read(CRTC_CURSOR_START) & 0xC0;
+        crtc_write(CRTC_CURSOR_START, start | disable | first);
+        let end = crtc_read(CRTC_CURSOR_END) & 0xE0;
+        crtc_write(CRTC_CURSOR_END, end | last);
+    }
+
+    // 칸 하나의 (문자, 속성)
+    pub fn cell(&self, col: usize, row: usize) -> (u8, u8) {
+        let character = self.screen[row][col];
+        (character.ascii_character, character.color_code.0)
+    }
+
+    pub fn write_cell(&mut self, col: usize, row: usize, byte: u8, attr: u8) {
+        self.set(row, col, ScreenChar {
+            ascii_character: byte,
+            color_code: ColorCode(attr),
+        });
+    }
 }
 
-// 색상 출력 헬퍼 함수
-pub fn print_colored(s: &str, fg: Color, bg: Color) {
-    let mut writer = WRITER.lock();
-    let old_color = writer.color_code;
-    writer.set_color(fg, bg);
-    writer.write_string(s);
-    writer.color_code = old_color;
+impl fmt::Write for Writer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_string(s);
+        Ok(())
+    }
 }
 
-pub fn clear_screen() {
-    WRITER.lock().clear_screen();
-}
\ No newline at end of file
+impl Default for Writer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
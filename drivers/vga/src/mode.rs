@@ -0,0 +1,96 @@
+// drivers/vga/src/mode.rs - VGA 텍스트 모드 전환 (80x25, 80x50, 90x60)
+use crate::regs::ModeRegs;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextMode {
+    Text80x25, // 9x16 글자, 720x400
+    Text80x50, // 9x8 글자, 720x400
+    Text90x60, // 8x8 글자, 720x480
+}
+
+impl TextMode {
+    pub const ALL: [TextMode; 3] = [TextMode::Text80x25, TextMode::Text80x50, TextMode::Text90x60];
+
+    pub fn width(self) -> usize {
+        match self {
+            TextMode::Text80x25 | TextMode::Text80x50 => 80,
+            TextMode::Text90x60 => 90,
+        }
+    }
+
+    pub fn height(self) -> usize {
+        match self {
+            TextMode::Text80x25 => 25,
+            TextMode::Text80x50 => 50,
+            TextMode::Text90x60 => 60,
+        }
+    }
+
+    // 글자 높이 (픽셀 줄)
+    pub fn font_height(self) -> usize {
+        match self {
+            TextMode::Text80x25 => 16,
+            TextMode::Text80x50 | TextMode::Text90x60 => 8,
+        }
+    }
+
+    pub fn from_size(width: usize, height: usize) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.width() == width && mode.height() == height)
+    }
+
+    fn regs(self) -> ModeRegs {
+        // 시퀀서/그래픽/속성 컨트롤러는 세 모드가 거의 같음
+        const SEQ_9DOT: [u8; 5] = [0x03, 0x00, 0x03, 0x00, 0x02];
+        const SEQ_8DOT: [u8; 5] = [0x03, 0x01, 0x03, 0x00, 0x02];
+        const GC: [u8; 9] = [0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x0E, 0x00, 0xFF];
+        const AC: [u8; 21] = [
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x14, 0x07,
+            0x38, 0x39, 0x3A, 0x3B, 0x3C, 0x3D, 0x3E, 0x3F,
+            0x0C, 0x00, 0x0F, 0x08, 0x00,
+        ];
+        match self {
+            TextMode::Text80x25 => ModeRegs {
+                misc: 0x67,
+                seq: SEQ_9DOT,
+                crtc: [
+                    0x5F, 0x4F, 0x50, 0x82, 0x55, 0x81, 0xBF, 0x1F,
+                    0x00, 0x4F, 0x0D, 0x0E, 0x00, 0x00, 0x00, 0x00,
+                    0x9C, 0x0E, 0x8F, 0x28, 0x1F, 0x96, 0xB9, 0xA3,
+                    0xFF,
+                ],
+                gc: GC,
+                ac: AC,
+            },
+            TextMode::Text80x50 => ModeRegs {
+                misc: 0x67,
+                seq: SEQ_9DOT,
+                crtc: [
+                    0x5F, 0x4F, 0x50, 0x82, 0x55, 0x81, 0xBF, 0x1F,
+                    0x00, 0x47, 0x06, 0x07, 0x00, 0x00, 0x00, 0x00,
+                    0x9C, 0x8E, 0x8F, 0x28, 0x1F, 0x96, 0xB9, 0xA3,
+                    0xFF,
+                ],
+                gc: GC,
+                ac: AC,
+            },
+            // 28MHz 클럭, 480줄
+            TextMode::Text90x60 => ModeRegs {
+                misc: 0xE7,
+                seq: SEQ_8DOT,
+                crtc: [
+                    0x6B, 0x59, 0x5A, 0x82, 0x60, 0x8D, 0x0B, 0x3E,
+                    0x00, 0x47, 0x06, 0x07, 0x00, 0x00, 0x00, 0x00,
+                    0xEA, 0x0C, 0xDF, 0x2D, 0x08, 0xE8, 0x05, 0xA3,
+                    0xFF,
+                ],
+                gc: GC,
+                ac: AC,
+            },
+        }
+    }
+}
+
+// 레지스터만 바꿈 (글꼴과 화면 내용은 호출한 쪽에서 다시 올림)
+pub fn set_text_mode(mode: TextMode) {
+    mode.regs().write();
+}
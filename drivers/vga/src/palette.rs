@@ -0,0 +1,47 @@
+// drivers/vga/src/palette.rs - DAC 팔레트 (색 번호 -> RGB, 각 성분 0-63)
+use x86_64::instructions::port::Port;
+
+const DAC_READ_INDEX: u16 = 0x3C7;
+const DAC_WRITE_INDEX: u16 = 0x3C8;
+const DAC_DATA: u16 = 0x3C9; // R, G, B 순서로 읽고 쓰면 다음 번호로 넘어감
+
+pub const ENTRIES: usize = 256;
+
+// 16색 VGA 기본 색 (텍스트 모드 색 번호 순서, 6비트 R, G, B)
+pub const STANDARD: [u8; 16 * 3] = [
+    0x00, 0x00, 0x00,  0x00, 0x00, 0x2A,  0x00, 0x2A, 0x00,  0x00, 0x2A, 0x2A,
+    0x2A, 0x00, 0x00,  0x2A, 0x00, 0x2A,  0x2A, 0x15, 0x00,  0x2A, 0x2A, 0x2A,
+    0x15, 0x15, 0x15,  0x15, 0x15, 0x3F,  0x15, 0x3F, 0x15,  0x15, 0x3F, 0x3F,
+    0x3F, 0x15, 0x15,  0x3F, 0x15, 0x3F,  0x3F, 0x3F, 0x15,  0x3F, 0x3F, 0x3F,
+];
+
+pub fn set_color(index: u8, r: u8, g: u8, b: u8) {
+    set_palette(index, &[r, g, b]);
+}
+
+pub fn color(index: u8) -> (u8, u8, u8) {
+    let mut rgb = [0; 3];
+    read_palette(index, &mut rgb);
+    (rgb[0], rgb[1], rgb[2])
+}
+
+// first번부터 3바이트(R, G, B)씩 연속으로 씀
+pub fn set_palette(first: u8, rgb: &[u8]) {
+    unsafe {
+        Port::<u8>::new(DAC_WRITE_INDEX).write(first);
+        let mut data = Port::<u8>::new(DAC_DATA);
+        for &value in rgb {
+            data.write(value & 0x3F);
+        }
+    }
+}
+
+pub fn read_palette(first: u8, rgb: &mut [u8]) {
+    unsafe {
+        Port::<u8>::new(DAC_READ_INDEX).write(first);
+        let mut data = Port::<u8>::new(DAC_DATA);
+        for value in rgb.iter_mut() {
+            *value = data.read();
+        }
+    }
+}
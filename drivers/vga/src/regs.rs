@@ -0,0 +1,136 @@
+// drivers/vga/src/regs.rs - VGA 레지스터 입출력 (Misc, 시퀀서, CRTC, 그래픽 컨트롤러, 속성 컨트롤러)
+use x86_64::instructions::port::Port;
+
+const MISC_WRITE: u16 = 0x3C2;
+const MISC_READ: u16 = 0x3CC;
+const SEQ_INDEX: u16 = 0x3C4;
+const SEQ_DATA: u16 = 0x3C5;
+const CRTC_INDEX: u16 = 0x3D4;
+const CRTC_DATA: u16 = 0x3D5;
+const GC_INDEX: u16 = 0x3CE;
+const GC_DATA: u16 = 0x3CF;
+const AC_INDEX: u16 = 0x3C0; // 인덱스와 데이터를 번갈아 씀
+const AC_READ: u16 = 0x3C1;
+const INPUT_STATUS: u16 = 0x3DA; // 읽으면 속성 컨트롤러가 인덱스 상태로 돌아감
+
+pub const SEQ_MAP_MASK: u8 = 0x02;
+pub const SEQ_MEMORY_MODE: u8 = 0x04;
+pub const GC_READ_MAP: u8 = 0x04;
+pub const GC_MODE: u8 = 0x05;
+pub const GC_MISC: u8 = 0x06;
+pub const GC_BIT_MASK: u8 = 0x08;
+pub const CRTC_MAX_SCAN_LINE: u8 = 0x09;
+const CRTC_END_HORIZONTAL_BLANK: u8 = 0x03;
+const CRTC_VERTICAL_RETRACE_END: u8 = 0x11;
+const AC_ENABLE_DISPLAY: u8 = 0x20;
+
+fn read(port: u16) -> u8 {
+    unsafe { Port::<u8>::new(port).read() }
+}
+
+fn write(port: u16, value: u8) {
+    unsafe { Port::<u8>::new(port).write(value) }
+}
+
+fn indexed_read(index_port: u16, data_port: u16, index: u8) -> u8 {
+    write(index_port, index);
+    read(data_port)
+}
+
+fn indexed_write(index_port: u16, data_port: u16, index: u8, value: u8) {
+    write(index_port, index);
+    write(data_port, value);
+}
+
+pub fn seq_read(index: u8) -> u8 {
+    indexed_read(SEQ_INDEX, SEQ_DATA, index)
+}
+
+pub fn seq_write(index: u8, value: u8) {
+    indexed_write(SEQ_INDEX, SEQ_DATA, index, value);
+}
+
+pub fn crtc_read(index: u8) -> u8 {
+    indexed_read(CRTC_INDEX, CRTC_DATA, index)
+}
+
+pub fn crtc_write(index: u8, value: u8) {
+    indexed_write(CRTC_INDEX, CRTC_DATA, index, value);
+}
+
+pub fn gc_read(index: u8) -> u8 {
+    indexed_read(GC_INDEX, GC_DATA, index)
+}
+
+pub fn gc_write(index: u8, value: u8) {
+    indexed_write(GC_INDEX, GC_DATA, index, value);
+}
+
+pub fn ac_read(index: u8) -> u8 {
+    read(INPUT_STATUS);
+    write(AC_INDEX, index);
+    let value = read(AC_READ);
+    read(INPUT_STATUS);
+    write(AC_INDEX, AC_ENABLE_DISPLAY);
+    value
+}
+
+// 한 모드를 이루는 레지스터 값 전체
+pub struct ModeRegs {
+    pub misc: u8,
+    pub seq: [u8; 5],
+    pub crtc: [u8; 25],
+    pub gc: [u8; 9],
+    pub ac: [u8; 21],
+}
+
+impl ModeRegs {
+    // 현재 하드웨어 상태
+    pub fn read() -> Self {
+        let mut regs = ModeRegs { misc: read(MISC_READ), seq: [0; 5], crtc: [0; 25], gc: [0; 9], ac: [0; 21] };
+        for (i, value) in regs.seq.iter_mut().enumerate() {
+            *value = seq_read(i as u8);
+        }
+        for (i, value) in regs.crtc.iter_mut().enumerate() {
+            *value = crtc_read(i as u8);
+        }
+        for (i, value) in regs.gc.iter_mut().enumerate() {
+            *value = gc_read(i as u8);
+        }
+        for (i, value) in regs.ac.iter_mut().enumerate() {
+            *value = ac_read(i as u8);
+        }
+        regs
+    }
+
+    pub fn write(&self) {
+        write(MISC_WRITE, self.misc);
+        for (i, &value) in self.seq.iter().enumerate() {
+            seq_write(i as u8, value);
+        }
+
+        // CRTC 0-7 쓰기 잠금 해제 (잠금 비트가 다시 켜지지 않도록 값도 고침)
+        crtc_write(CRTC_END_HORIZONTAL_BLANK, crtc_read(CRTC_END_HORIZONTAL_BLANK) | 0x80);
+        crtc_write(CRTC_VERTICAL_RETRACE_END, crtc_read(CRTC_VERTICAL_RETRACE_END) & !0x80);
+        for (i, &value) in self.crtc.iter().enumerate() {
+            let value = match i as u8 {
+                CRTC_END_HORIZONTAL_BLANK => value | 0x80,
+                CRTC_VERTICAL_RETRACE_END => value & !0x80,
+                _ => value,
+            };
+            crtc_write(i as u8, value);
+        }
+
+        for (i, &value) in self.gc.iter().enumerate() {
+            gc_write(i as u8, value);
+        }
+        for (i, &value) in self.ac.iter().enumerate() {
+            read(INPUT_STATUS);
+            write(AC_INDEX, i as u8);
+            write(AC_INDEX, value);
+        }
+        // 팔레트 잠금, 화면 켜기
+        read(INPUT_STATUS);
+        write(AC_INDEX, AC_ENABLE_DISPLAY);
+    }
+}
@@ -0,0 +1,94 @@
+// kernel/src/bootlog.rs - 부팅 단계별 TSC 시간 측정과 성공/실패 기록
+use core::arch::x86_64::_rdtsc;
+use core::sync::atomic::{AtomicU64, Ordering};
+use log::{info, warn};
+use spin::Mutex;
+
+const MAX_STAGES: usize = 16;
+const CALIBRATION_TICKS: u64 = 10; // 100ms
+
+#[derive(Debug, Clone, Copy)]
+pub struct Stage {
+    pub name: &'static str,
+    pub start: u64, // 부팅 시작부터의 TSC 사이클
+    pub cycles: u64,
+    pub ok: bool,
+}
+
+struct BootLog {
+    stages: [Option<Stage>; MAX_STAGES],
+    len: usize,
+}
+
+static BOOT_TSC: AtomicU64 = AtomicU64::new(0);
+static TSC_PER_MS: AtomicU64 = AtomicU64::new(0);
+static LOG: Mutex<BootLog> = Mutex::new(BootLog { stages: [None; MAX_STAGES], len: 0 });
+
+fn rdtsc() -> u64 {
+    unsafe { _rdtsc() }
+}
+
+// _start 맨 처음에 호출 (타임라인의 0 지점)
+pub fn start() {
+    BOOT_TSC.store(rdtsc(), Ordering::Relaxed);
+}
+
+// 단계 하나 실행 -> check가 결과를 보고 성공 여부 판단
+pub fn stage<T>(name: &'static str, f: impl FnOnce() -> T, check: impl FnOnce(&T) -> bool) -> T {
+    info!("{}...", name);
+    let begin = rdtsc();
+    let result = f();
+    let end = rdtsc();
+    let ok = check(&result);
+
+    if !ok {
+        warn!("{} failed", name);
+    }
+    let mut log = LOG.lock();
+    if log.len < MAX_STAGES {
+        let len = log.len;
+        log.stages[len] = Some(Stage {
+            name,
+            start: begin - BOOT_TSC.load(Ordering::Relaxed),
+            cycles: end - begin,
+            ok,
+        });
+        log.len += 1;
+    }
+    result
+}
+
+// 기록된 단계 (순서대로)
+pub fn stages() -> impl Iterator<Item = Stage> {
+    let log = LOG.lock();
+    let stages = log.stages;
+    stages.into_iter().take(log.len).flatten()
+}
+
+// 마지막 단계가 끝난 시점까지의 사이클
+pub fn total_cycles() -> u64 {
+    stages().map(|s| s.start + s.cycles).max().unwrap_or(0)
+}
+
+// 1ms당 TSC 사이클. 처음 호출 시 타이머 틱으로 측정 (인터럽트가 켜져 있어야 함)
+pub fn tsc_per_ms() -> u64 {
+    let cached = TSC_PER_MS.load(Ordering::Relaxed);
+    if cached != 0 {
+        return cached;
+    }
+
+    // 틱 경계에서 시작
+    let first = crate::interrupts::get_timer_ticks();
+    while crate::interrupts::get_timer_ticks() == first {
+        x86_64::instructions::hlt();
+    }
+    let begin = rdtsc();
+    let until = first + 1 + CALIBRATION_TICKS;
+    while crate::interrupts::get_timer_ticks() < until {
+        x86_64::instructions::hlt();
+    }
+    let per_ms = ((rdtsc() - begin) / (CALIBRATION_TICKS * 10)).max(1);
+
+    TSC_PER_MS.store(per_ms, Ordering::Relaxed);
+    per_ms
+}
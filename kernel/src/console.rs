@@ -0,0 +1,380 @@
+// kernel/src/console.rs - 콘솔 추상화와 커널 전체 print!/println!
+//
+// 백엔드: VGA 텍스트 (vga_driver::Writer), 시리얼 (serial::SerialConsole), 프레임버퍼 (fbcon)
+// VGA 텍스트는 가상 터미널 VT_COUNT개 (Alt+F1..), 화면에는 활성 터미널만 보임
+// VGA 그래픽 모드(enter_graphics)에 있는 동안에는 어느 터미널도 보이지 않음
+// 320x200 256색 모드에서는 A0000의 프레임버퍼 콘솔이 기본 콘솔 (print!, 패닉 화면)
+// 출력은 기본 콘솔로 가고, 켜져 있으면 시리얼에도 같은 내용을 씀
+use alloc::boxed::Box;
+use core::fmt;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use spin::{Mutex, Once};
+use x86_64::instructions::interrupts;
+use kernel_core::font::halve_height;
+use vga_driver::font::{self, GLYPHS};
+use vga_driver::graphics::{self, Canvas, GraphicsMode};
+use vga_driver::mode::{self, TextMode};
+use vga_driver::palette;
+use vga_driver::{CursorShape, Writer};
+use crate::fbcon::{Font, Framebuffer, FramebufferConsole};
+
+// 문자 칸 단위 콘솔. 색은 4비트 VGA 색 번호
+pub trait Console: Send {
+    // (열, 행)
+    fn size(&self) -> (usize, usize);
+    // 커서 위치부터 현재 색으로 출력 (\n, \r, \t, \x08 처리, 줄 끝에서 줄바꿈, 화면 끝에서 스크롤)
+    fn write_str(&mut self, s: &str);
+    fn cursor(&self) -> (usize, usize);
+    fn set_cursor(&mut self, col: usize, row: usize);
+    fn set_color(&mut self, fg: u8);
+    // 화면 전체의 배경색을 바꾸고 이후 출력에도 적용
+    fn set_background(&mut self, bg: u8);
+    fn clear_row(&mut self, row: usize);
+    // 화면을 지우고 커서를 맨 위로
+    fn clear(&mut self);
+    // 커서 위치 표시 (입력 줄)
+    fn show_cursor(&mut self, _visible: bool) {}
+    // 스크롤백 보기: lines만큼 과거(양수) 또는 최근(음수) 쪽으로. 출력하면 맨 아래로 돌아감
+    fn scroll_view(&mut self, _lines: isize) {}
+}
+
+impl Console for Writer {
+    fn size(&self) -> (usize, usize) {
+        Writer::size(self)
+    }
+
+    fn write_str(&mut self, s: &str) {
+        self.write_string(s);
+    }
+
+    fn cursor(&self) -> (usize, usize) {
+        self.position()
+    }
+
+    fn set_cursor(&mut self, col: usize, row: usize) {
+        self.set_position(col, row);
+    }
+
+    fn set_color(&mut self, fg: u8) {
+        self.set_color_code((self.color_code() & 0xF0) | (fg & 0x0F));
+    }
+
+    fn set_background(&mut self, bg: u8) {
+        Writer::set_background(self, bg & 0x0F);
+    }
+
+    fn clear_row(&mut self, row: usize) {
+        Writer::clear_row(self, row);
+    }
+
+    fn clear(&mut self) {
+        self.clear_screen();
+    }
+
+    fn show_cursor(&mut self, visible: bool) {
+        match (visible, self.cursor_visible()) {
+            (true, false) => self.enable_cursor(CursorShape::Underline),
+            (false, true) => self.disable_cursor(),
+            _ => {},
+        }
+    }
+
+    fn scroll_view(&mut self, lines: isize) {
+        Writer::scroll_view(self, lines);
+    }
+}
+
+pub const VT_COUNT: usize = 6;
+
+const fn terminals() -> [Mutex<Writer>; VT_COUNT] {
+    let mut vts = [const { Mutex::new(Writer::new()) }; VT_COUNT];
+    vts[0] = Mutex::new(Writer::new().activated());
+    vts
+}
+
+static TERMINALS: [Mutex<Writer>; VT_COUNT] = terminals();
+static ACTIVE_VT: AtomicUsize = AtomicUsize::new(0);
+
+// None이면 활성 가상 터미널
+static PRIMARY: Mutex<Option<&'static Mutex<dyn Console>>> = Mutex::new(None);
+static MIRROR_SERIAL: AtomicBool = AtomicBool::new(true);
+
+fn primary() -> &'static Mutex<dyn Console> {
+    match *PRIMARY.lock() {
+        Some(console) => console,
+        None => active_terminal(),
+    }
+}
+
+pub fn active_vt() -> usize {
+    ACTIVE_VT.load(Ordering::Relaxed)
+}
+
+// 화면에 보이는 VGA 텍스트 터미널 (칸 단위 직접 접근용)
+pub fn active_terminal() -> &'static Mutex<Writer> {
+    &TERMINALS[active_vt()]
+}
+
+// 가상 터미널 전환: 이전 터미널은 메모리에만 쓰고 새 터미널 내용을 VGA로 복사
+pub fn switch_vt(vt: usize) {
+    interrupts::without_interrupts(|| {
+        let old = active_vt();
+        if vt == old || vt >= VT_COUNT || in_graphics() {
+            return;
+        }
+        TERMINALS[old].lock().deactivate();
+        ACTIVE_VT.store(vt, Ordering::Relaxed);
+        TERMINALS[vt].lock().activate();
+    });
+}
+
+// 현재 텍스트 모드와 모드별 글꼴 (8줄 글꼴은 처음에 BIOS 8x16 글꼴을 줄여서 만듦)
+// 그래픽 모드에 있는 동안에는 텍스트 모드의 팔레트를 보관
+struct TextScreen {
+    mode: TextMode,
+    captured: bool,
+    bios16: [u8; GLYPHS * 16], // 처음 읽은 BIOS 글꼴 (reset_font)
+    font16: [u8; GLYPHS * 16],
+    font8: [u8; GLYPHS * 8],
+    graphics: bool,
+    palette: [u8; palette::ENTRIES * 3],
+}
+
+impl TextScreen {
+    fn capture_bios_font(&mut self) {
+        if !self.captured {
+            font::read_font(&mut self.bios16, 16);
+            self.font16 = self.bios16;
+            halve_height(&self.font16, 16, &mut self.font8);
+            self.captured = true;
+        }
+    }
+
+    fn upload(&self) {
+        match self.mode.font_height() {
+            16 => font::load_font(&self.font16, 16),
+            _ => font::load_font(&self.font8, 8),
+        }
+    }
+}
+
+static TEXT_SCREEN: Mutex<TextScreen> = Mutex::new(TextScreen {
+    mode: TextMode::Text80x25,
+    captured: false,
+    bios16: [0; GLYPHS * 16],
+    font16: [0; GLYPHS * 16],
+    font8: [0; GLYPHS * 8],
+    graphics: false,
+    palette: [0; palette::ENTRIES * 3],
+});
+
+pub fn text_mode() -> TextMode {
+    TEXT_SCREEN.lock().mode
+}
+
+pub fn in_graphics() -> bool {
+    TEXT_SCREEN.lock().graphics
+}
+
+// VGA 텍스트 모드 전환: 레지스터를 바꾸고 글꼴을 다시 올린 뒤 모든 가상 터미널 크기를 맞춤
+// 그래픽 모드에서 돌아올 때는 팔레트도 되돌리고 활성 터미널을 다시 그림
+pub fn set_text_mode(mode: TextMode) {
+    interrupts::without_interrupts(|| {
+        let mut screen = TEXT_SCREEN.lock();
+        screen.capture_bios_font();
+        mode::set_text_mode(mode);
+        screen.mode = mode;
+        screen.upload();
+        let from_graphics = core::mem::replace(&mut screen.graphics, false);
+        if from_graphics {
+            palette::set_palette(0, &screen.palette);
+            set_primary(None);
+        }
+        for terminal in TERMINALS.iter() {
+            terminal.lock().resize(mode.width(), mode.height());
+        }
+        if from_graphics {
+            active_terminal().lock().activate();
+        }
+    });
+}
+
+// 그래픽 모드 전환. 가상 터미널은 메모리에만 계속 기록되고 leave_graphics로 돌아오면 다시 보임
+pub fn enter_graphics(mode: GraphicsMode) -> Canvas {
+    interrupts::without_interrupts(|| {
+        let mut screen = TEXT_SCREEN.lock();
+        // 그래픽 모드가 글꼴 평면을 덮어쓰기 전에 보관
+        screen.capture_bios_font();
+        if !screen.graphics {
+            palette::read_palette(0, &mut screen.palette);
+            screen.graphics = true;
+            active_terminal().lock().deactivate();
+        }
+        let canvas = graphics::set_graphics_mode(mode);
+        let primary = match mode {
+            GraphicsMode::Mode320x200x256 => {
+                let console = GRAPHICS_CONSOLE.call_once(|| {
+                    // 글꼴은 텍스트 모드의 8줄 글꼴 복사본
+                    let glyphs: &'static [u8] = Box::leak(Box::new(screen.font8));
+                    let fb = Framebuffer { base: 0xA0000 as *mut u8, width: 320, height: 200, pitch: 320, bpp: 8 };
+                    Mutex::new(unsafe { FramebufferConsole::new(fb, Font { glyphs, height: 8 }) })
+                });
+                console.lock().clear();
+                Some(console as &'static Mutex<dyn Console>)
+            },
+            // 평면 모드는 프레임버퍼 콘솔로 쓸 수 없음
+            GraphicsMode::Mode640x480x16 => None,
+        };
+        set_primary(primary);
+        canvas
+    })
+}
+
+// 320x200 256색 모드의 텍스트 콘솔 (처음 들어갈 때 만들고 이후 재사용)
+static GRAPHICS_CONSOLE: Once<Mutex<FramebufferConsole>> = Once::new();
+
+// 그래픽 모드에 들어가기 전의 텍스트 모드로 (글꼴, 팔레트, 화면 내용 복원)
+pub fn leave_graphics() {
+    if in_graphics() {
+        set_text_mode(text_mode());
+    }
+}
+
+// 부팅 때의 BIOS 8x16 글꼴 (buf 길이는 256 * 16 이상)
+pub fn bios_font(buf: &mut [u8]) {
+    interrupts::without_interrupts(|| {
+        let mut screen = TEXT_SCREEN.lock();
+        screen.capture_bios_font();
+        let len = buf.len().min(screen.bios16.len());
+        buf[..len].copy_from_slice(&screen.bios16[..len]);
+    })
+}
+
+// 사용자 글꼴을 버리고 BIOS 글꼴로 (8줄 글꼴도 다시 만듦)
+pub fn reset_font() {
+    interrupts::without_interrupts(|| {
+        let mut screen = TEXT_SCREEN.lock();
+        screen.capture_bios_font();
+        let screen = &mut *screen;
+        screen.font16 = screen.bios16;
+        halve_height(&screen.font16, 16, &mut screen.font8);
+        if !screen.graphics {
+            screen.upload();
+        }
+    })
+}
+
+// 사용자 글꼴 (글자당 height바이트, 8 또는 16). 같은 높이의 모드에서 쓰이고 모드를 바꿔도 유지됨
+pub fn load_font(glyphs: &[u8], height: usize) -> bool {
+    interrupts::without_interrupts(|| {
+        let mut screen = TEXT_SCREEN.lock();
+        screen.capture_bios_font();
+        let target: &mut [u8] = match height {
+            16 => &mut screen.font16,
+            8 => &mut screen.font8,
+            _ => return false,
+        };
+        let len = glyphs.len().min(target.len());
+        target[..len].copy_from_slice(&glyphs[..len]);
+        if !screen.graphics && screen.mode.font_height() == height {
+            screen.upload();
+        }
+        true
+    })
+}
+
+// 화면에 보이는지와 상관없이 가상 터미널 vt에 출력
+pub fn with_vt<R>(vt: usize, f: impl FnOnce(&mut dyn Console) -> R) -> R {
+    interrupts::without_interrupts(|| f(&mut *TERMINALS[vt].lock()))
+}
+
+// 기본 콘솔 교체 (예: 그래픽 모드의 프레임버퍼 콘솔)
+pub fn set_primary(console: Option<&'static Mutex<dyn Console>>) {
+    interrupts::without_interrupts(|| *PRIMARY.lock() = console);
+}
+
+// println! 출력을 시리얼에도 보낼지
+pub fn set_serial_mirror(on: bool) {
+    MIRROR_SERIAL.store(on, Ordering::Relaxed);
+}
+
+pub fn serial_mirror() -> bool {
+    MIRROR_SERIAL.load(Ordering::Relaxed)
+}
+
+// 기본 콘솔을 잠그고 f 실행 (인터럽트 핸들러와 겹치지 않도록 인터럽트 차단)
+pub fn with<R>(f: impl FnOnce(&mut dyn Console) -> R) -> R {
+    interrupts::without_interrupts(|| f(&mut *primary().lock()))
+}
+
+// 인터럽트 핸들러용: 사용 중이면 기다리지 않고 None
+pub fn try_with<R>(f: impl FnOnce(&mut dyn Console) -> R) -> Option<R> {
+    interrupts::without_interrupts(|| {
+        let console = PRIMARY.try_lock()?.unwrap_or(active_terminal());
+        let mut guard = console.try_lock()?;
+        Some(f(&mut *guard))
+    })
+}
+
+// 패닉/폴트 경로: 잠금을 가진 코드로 돌아가지 않으므로 강제로 풀고 사용
+pub fn emergency<R>(f: impl FnOnce(&mut dyn Console) -> R) -> R {
+    interrupts::disable();
+    if PRIMARY.is_locked() {
+        unsafe { PRIMARY.force_unlock(); }
+    }
+    let console = primary();
+    if console.is_locked() {
+        unsafe { console.force_unlock(); }
+    }
+    let mut guard = console.lock();
+    f(&mut *guard)
+}
+
+// 빨간 화면 가운데에 메시지 (패닉, 더블 폴트 등)
+pub fn crash_screen(title: &str, detail: fmt::Arguments) {
+    emergency(|console| {
+        let (width, height) = console.size();
+        console.set_background(0x4);
+        console.set_color(0x0F);
+        console.clear();
+        console.show_cursor(false);
+        console.set_cursor(width.saturating_sub(title.len()) / 2, height / 2 - 2);
+        console.write_str(title);
+        console.set_cursor(0, height / 2);
+        let _ = fmt::write(&mut Adapter(console), detail);
+    });
+}
+
+// dyn Console -> fmt::Write
+pub struct Adapter<'a>(pub &'a mut dyn Console);
+
+impl fmt::Write for Adapter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_str(s);
+        Ok(())
+    }
+}
+
+#[doc(hidden)]
+pub fn _print(args: fmt::Arguments) {
+    use core::fmt::Write;
+    interrupts::without_interrupts(|| {
+        let _ = Adapter(&mut *primary().lock()).write_fmt(args);
+        if serial_mirror() {
+            let _ = crate::serial::SERIAL_CONSOLE.lock().write_fmt(args);
+        }
+    });
+}
+
+#[macro_export]
+macro_rules! print {
+    ($($arg:tt)*) => ($crate::console::_print(format_args!($($arg)*)));
+}
+
+#[macro_export]
+macro_rules! println {
+    () => ($crate::print!("\n"));
+    ($($arg:tt)*) => ({
+        $crate::print!("{}\n", format_args!($($arg)*));
+    });
+}
@@ -0,0 +1,234 @@
+// kernel/src/crashdump.rs - 패닉/폴트 시 시리얼로 기계가 읽을 수 있는 크래시 기록 출력
+//
+// 형식 (한 줄에 항목 하나, tools/crashdecode가 해석):
+//   -----BEGIN AEROGEL CRASH-----
+//   version 1
+//   reason <panic|double-fault|page-fault|alloc-error>
+//   message <한 줄 텍스트>
+//   tick <타이머 틱>
+//   reg <이름> <16진수>
+//   stack <주소> <값>
+//   bt <번호> <리턴 주소>
+//   log <틱> <수준> <대상> <메시지>
+//   heap <크기> <사용> <남음>
+//   -----END AEROGEL CRASH-----
+use core::arch::{asm, global_asm};
+use core::fmt::{self, Write};
+use core::sync::atomic::{AtomicBool, Ordering};
+use uart_16550::SerialPort;
+use crate::interrupts::TrapFrame;
+
+const VERSION: u32 = 1;
+const STACK_WORDS: usize = 32;
+const MAX_FRAMES: usize = 32;
+const LOG_LINES: usize = 16;
+// 프레임 하나가 이보다 크면 RBP 체인이 깨진 것으로 봄
+const MAX_FRAME_SIZE: u64 = 64 * 1024;
+
+static DUMPING: AtomicBool = AtomicBool::new(false);
+
+// 페이지 폴트가 나도 안전한 메모리 읽기. 폴트 시 page_fault_handler가
+// fixup()으로 crash_probe_done으로 건너뛰고 rax = 1로 실패를 알림
+global_asm!(
+    ".global crash_probe_read",
+    "crash_probe_read:",
+    "    xor eax, eax",
+    ".global crash_probe_insn",
+    "crash_probe_insn:",
+    "    mov rdx, [rdi]",
+    "    mov [rsi], rdx",
+    ".global crash_probe_done",
+    "crash_probe_done:",
+    "    ret",
+);
+
+extern "C" {
+    fn crash_probe_read(addr: u64, out: *mut u64) -> u64;
+    fn crash_probe_insn();
+    fn crash_probe_done();
+}
+
+fn is_canonical(addr: u64) -> bool {
+    let top = addr >> 47;
+    top == 0 || top == 0x1FFFF
+}
+
+fn read_u64(addr: u64) -> Option<u64> {
+    if addr < 0x1000 || !addr.is_multiple_of(8) || !is_canonical(addr) || !is_canonical(addr + 7) {
+        return None;
+    }
+    let mut value = 0;
+    match unsafe { crash_probe_read(addr, &mut value) } {
+        0 => Some(value),
+        _ => None,
+    }
+}
+
+// 한 바이트 읽기 (바이트를 포함한 정렬된 8바이트를 읽음). 매핑되지 않은 주소면 None
+pub fn probe_byte(addr: u64) -> Option<u8> {
+    let word = read_u64(addr & !7)?;
+    Some((word >> ((addr & 7) * 8)) as u8)
+}
+
+// 페이지 폴트 핸들러에서 호출 -> 읽기 시도 중의 폴트면 건너뛰고 true
+pub fn fixup(frame: &mut TrapFrame) -> bool {
+    if frame.rip == crash_probe_insn as *const () as u64 {
+        frame.rip = crash_probe_done as *const () as u64;
+        frame.rax = 1;
+        true
+    } else {
+        false
+    }
+}
+
+// 현재 레지스터 (패닉처럼 트랩 프레임이 없을 때)
+#[inline(always)]
+pub fn capture_registers() -> TrapFrame {
+    let mut frame = TrapFrame::default();
+    unsafe {
+        asm!(
+            "mov [rdi + 0x00], r15",
+            "mov [rdi + 0x08], r14",
+            "mov [rdi + 0x10], r13",
+            "mov [rdi + 0x18], r12",
+            "mov [rdi + 0x20], r11",
+            "mov [rdi + 0x28], r10",
+            "mov [rdi + 0x30], r9",
+            "mov [rdi + 0x38], r8",
+            "mov [rdi + 0x40], rbp",
+            "mov [rdi + 0x48], rdi",
+            "mov [rdi + 0x50], rsi",
+            "mov [rdi + 0x58], rdx",
+            "mov [rdi + 0x60], rcx",
+            "mov [rdi + 0x68], rbx",
+            "mov [rdi + 0x70], rax",
+            in("rdi") &mut frame as *mut TrapFrame,
+            options(nostack, preserves_flags),
+        );
+        asm!("lea {}, [rip]", out(reg) frame.rip, options(nomem, nostack, preserves_flags));
+        asm!("mov {}, rsp", out(reg) frame.rsp, options(nomem, nostack, preserves_flags));
+        asm!("pushfq", "pop {}", out(reg) frame.rflags, options(preserves_flags));
+        asm!("mov {:r}, cs", out(reg) frame.cs, options(nomem, nostack, preserves_flags));
+        asm!("mov {:r}, ss", out(reg) frame.ss, options(nomem, nostack, preserves_flags));
+    }
+    frame
+}
+
+// 잠금 없이 COM1에 직접 씀 (크래시 시 SERIAL1이 잠겨 있을 수 있음)
+struct CrashWriter(SerialPort);
+
+impl CrashWriter {
+    fn new() -> Self {
+        CrashWriter(unsafe { SerialPort::new(crate::serial::COM1) })
+    }
+
+    fn line(&mut self, args: fmt::Arguments) {
+        let _ = self.0.write_fmt(args);
+        let _ = self.0.write_str("\r\n");
+    }
+}
+
+// 메시지를 한 줄로 (줄바꿈 이스케이프)
+struct OneLine<'a>(&'a mut CrashWriter);
+
+impl Write for OneLine<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for ch in s.chars() {
+            match ch {
+                '\n' => self.0 .0.write_str("\\n")?,
+                '\r' => {},
+                '\\' => self.0 .0.write_str("\\\\")?,
+                _ => self.0 .0.write_char(ch)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// 크래시 기록 출력. 덤프 중 다시 폴트가 나면 기록을 닫고 멈춤
+pub fn dump(reason: &str, message: fmt::Arguments, regs: &TrapFrame) {
+    let mut out = CrashWriter::new();
+
+    if DUMPING.swap(true, Ordering::SeqCst) {
+        out.line(format_args!("nested {}", reason));
+        out.line(format_args!("-----END AEROGEL CRASH-----"));
+        crate::hlt_loop();
+    }
+
+    out.line(format_args!(""));
+    out.line(format_args!("-----BEGIN AEROGEL CRASH-----"));
+    out.line(format_args!("version {}", VERSION));
+    out.line(format_args!("reason {}", reason));
+    let _ = out.0.write_str("message ");
+    let _ = OneLine(&mut out).write_fmt(message);
+    out.line(format_args!(""));
+    out.line(format_args!("tick {}", crate::interrupts::get_timer_ticks()));
+
+    write_registers(&mut out, regs);
+    write_stack(&mut out, regs.rsp);
+    write_backtrace(&mut out, regs.rip, regs.rbp);
+    write_log(&mut out);
+    match crate::memory::try_heap_stats() {
+        Some((size, used, free)) => out.line(format_args!("heap {} {} {}", size, used, free)),
+        None => out.line(format_args!("heap locked")),
+    }
+
+    out.line(format_args!("-----END AEROGEL CRASH-----"));
+}
+
+fn write_registers(out: &mut CrashWriter, regs: &TrapFrame) {
+    let named = [
+        ("rip", regs.rip), ("rsp", regs.rsp), ("rbp", regs.rbp), ("rflags", regs.rflags),
+        ("rax", regs.rax), ("rbx", regs.rbx), ("rcx", regs.rcx), ("rdx", regs.rdx),
+        ("rsi", regs.rsi), ("rdi", regs.rdi), ("r8", regs.r8), ("r9", regs.r9),
+        ("r10", regs.r10), ("r11", regs.r11), ("r12", regs.r12), ("r13", regs.r13),
+        ("r14", regs.r14), ("r15", regs.r15), ("cs", regs.cs), ("ss", regs.ss),
+        ("error", regs.error_code),
+    ];
+    for (name, value) in named {
+        out.line(format_args!("reg {} {:016x}", name, value));
+    }
+    let cr2 = x86_64::registers::control::Cr2::read().as_u64();
+    out.line(format_args!("reg cr2 {:016x}", cr2));
+    let cr3 = x86_64::registers::control::Cr3::read().0.start_address().as_u64();
+    out.line(format_args!("reg cr3 {:016x}", cr3));
+}
+
+fn write_stack(out: &mut CrashWriter, rsp: u64) {
+    for i in 0..STACK_WORDS as u64 {
+        let addr = rsp.wrapping_add(i * 8);
+        match read_u64(addr) {
+            Some(value) => out.line(format_args!("stack {:016x} {:016x}", addr, value)),
+            None => break,
+        }
+    }
+}
+
+// RBP 체인을 따라감 (타깃 JSON에서 frame-pointer = always)
+fn write_backtrace(out: &mut CrashWriter, rip: u64, mut rbp: u64) {
+    out.line(format_args!("bt 0 {:016x}", rip));
+    for depth in 1..MAX_FRAMES {
+        let (Some(next), Some(ret)) = (read_u64(rbp), read_u64(rbp.wrapping_add(8))) else {
+            break;
+        };
+        if ret == 0 {
+            break;
+        }
+        out.line(format_args!("bt {} {:016x}", depth, ret));
+        if next <= rbp || next - rbp > MAX_FRAME_SIZE {
+            break;
+        }
+        rbp = next;
+    }
+}
+
+fn write_log(out: &mut CrashWriter) {
+    let complete = crate::klog::try_for_each_recent(LOG_LINES, |entry| {
+        let _ = write!(out.0, "log {} {} {} ", entry.timestamp, entry.level, entry.target);
+        let _ = OneLine(out).write_str(entry.message());
+        out.line(format_args!(""));
+    });
+    if !complete {
+        out.line(format_args!("log locked"));
+    }
+}
@@ -0,0 +1,223 @@
+// kernel/src/fbcon.rs - 선형 프레임버퍼 위의 텍스트 콘솔 (폭 8픽셀 비트맵 글꼴)
+use alloc::vec;
+use alloc::vec::Vec;
+use crate::console::Console;
+use kernel_core::cp437;
+
+// 16색 VGA 기본 팔레트 (32bpp용 0xRRGGBB)
+const RGB: [u32; 16] = [
+    0x000000, 0x0000AA, 0x00AA00, 0x00AAAA, 0xAA0000, 0xAA00AA, 0xAA5500, 0xAAAAAA,
+    0x555555, 0x5555FF, 0x55FF55, 0x55FFFF, 0xFF5555, 0xFF55FF, 0xFFFF55, 0xFFFFFF,
+];
+const TAB_WIDTH: usize = 8;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Framebuffer {
+    pub base: *mut u8,
+    pub width: usize,  // 픽셀
+    pub height: usize,
+    pub pitch: usize,  // 한 줄의 바이트 수
+    pub bpp: usize,    // 8 (팔레트 번호) 또는 32
+}
+
+// 프레임버퍼는 콘솔 잠금 안에서만 접근
+unsafe impl Send for Framebuffer {}
+
+impl Framebuffer {
+    fn put_pixel(&self, x: usize, y: usize, color: u8) {
+        unsafe {
+            let row = self.base.add(y * self.pitch);
+            match self.bpp {
+                8 => row.add(x).write_volatile(color),
+                _ => (row as *mut u32).add(x).write_volatile(RGB[(color & 0x0F) as usize]),
+            }
+        }
+    }
+}
+
+// 글자 하나당 height 바이트 (한 바이트가 8픽셀 한 줄, 최상위 비트가 왼쪽), CP437 순서 256자
+#[derive(Debug, Clone, Copy)]
+pub struct Font {
+    pub glyphs: &'static [u8],
+    pub height: usize,
+}
+
+pub struct FramebufferConsole {
+    fb: Framebuffer,
+    font: Font,
+    cols: usize,
+    rows: usize,
+    col: usize,
+    row: usize,
+    fg: u8,
+    bg: u8,
+    cells: Vec<(u8, u8, u8)>, // 칸마다 (문자, 전경, 배경): 커서와 배경색 변경 시 다시 그림
+    cursor_visible: bool,
+    cursor_drawn: Option<(usize, usize)>, // 반전해서 그려 둔 커서 칸
+}
+
+impl FramebufferConsole {
+    /// # Safety
+    /// base부터 pitch * height 바이트가 매핑된 프레임버퍼여야 함
+    pub unsafe fn new(fb: Framebuffer, font: Font) -> Self {
+        let cols = fb.width / 8;
+        let rows = fb.height / font.height;
+        FramebufferConsole {
+            fb, font, cols, rows,
+            col: 0, row: 0,
+            fg: 0x07, bg: 0x00,
+            cells: vec![(b' ', 0x07, 0x00); cols * rows],
+            cursor_visible: false,
+            cursor_drawn: None,
+        }
+    }
+
+    fn draw(&mut self, col: usize, row: usize) {
+        let (byte, mut fg, mut bg) = self.cells[row * self.cols + col];
+        if self.cursor_drawn == Some((col, row)) {
+            core::mem::swap(&mut fg, &mut bg);
+        }
+        let glyph = &self.font.glyphs[byte as usize * self.font.height..][..self.font.height];
+        for (y, bits) in glyph.iter().enumerate() {
+            for x in 0..8 {
+                let color = if bits & (0x80 >> x) != 0 { fg } else { bg };
+                self.fb.put_pixel(col * 8 + x, row * self.font.height + y, color);
+            }
+        }
+    }
+
+    fn put(&mut self, col: usize, row: usize, byte: u8) {
+        self.cells[row * self.cols + col] = (byte, self.fg, self.bg);
+        self.draw(col, row);
+    }
+
+    // 하드웨어 커서가 없으므로 커서 칸을 반전해서 그림
+    fn hide_cursor(&mut self) {
+        if let Some((col, row)) = self.cursor_drawn.take() {
+            self.draw(col, row);
+        }
+    }
+
+    fn place_cursor(&mut self) {
+        if self.cursor_visible {
+            self.cursor_drawn = Some((self.col.min(self.cols - 1), self.row));
+            self.draw(self.col.min(self.cols - 1), self.row);
+        }
+    }
+
+    fn redraw(&mut self) {
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                self.draw(col, row);
+            }
+        }
+    }
+
+    fn blank_row(&mut self, row: usize) {
+        for col in 0..self.cols {
+            self.put(col, row, b' ');
+        }
+    }
+
+    fn new_line(&mut self) {
+        self.col = 0;
+        if self.row + 1 < self.rows {
+            self.row += 1;
+            return;
+        }
+        // 픽셀 단위로 한 글자 줄만큼 올림
+        let line_bytes = self.fb.pitch * self.font.height;
+        unsafe {
+            core::ptr::copy(self.fb.base.add(line_bytes), self.fb.base, line_bytes * (self.rows - 1));
+        }
+        self.cells.copy_within(self.cols.., 0);
+        self.blank_row(self.rows - 1);
+    }
+
+    fn write_byte(&mut self, byte: u8) {
+        match byte {
+            b'\n' => self.new_line(),
+            b'\r' => self.col = 0,
+            b'\t' => {
+                let next = (self.col / TAB_WIDTH + 1) * TAB_WIDTH;
+                while self.col < next.min(self.cols) {
+                    self.write_glyph(b' ');
+                }
+            },
+            0x08 => self.col = self.col.saturating_sub(1),
+            byte => self.write_glyph(byte),
+        }
+    }
+
+    fn write_glyph(&mut self, byte: u8) {
+        if self.col >= self.cols {
+            self.new_line();
+        }
+        self.put(self.col, self.row, byte);
+        self.col += 1;
+    }
+}
+
+impl Console for FramebufferConsole {
+    fn size(&self) -> (usize, usize) {
+        (self.cols, self.rows)
+    }
+
+    fn write_str(&mut self, s: &str) {
+        self.hide_cursor();
+        for ch in s.chars() {
+            match ch {
+                '\n' | '\r' | '\t' | '\x08' => self.write_byte(ch as u8),
+                _ => self.write_glyph(cp437::encode(ch)), // 표에 없으면 ■
+            }
+        }
+        self.place_cursor();
+    }
+
+    fn cursor(&self) -> (usize, usize) {
+        (self.col, self.row)
+    }
+
+    fn set_cursor(&mut self, col: usize, row: usize) {
+        self.hide_cursor();
+        self.col = col.min(self.cols);
+        self.row = row.min(self.rows - 1);
+        self.place_cursor();
+    }
+
+    fn set_color(&mut self, fg: u8) {
+        self.fg = fg & 0x0F;
+    }
+
+    fn set_background(&mut self, bg: u8) {
+        self.hide_cursor();
+        self.bg = bg & 0x0F;
+        for cell in self.cells.iter_mut() {
+            cell.2 = self.bg;
+        }
+        self.redraw();
+        self.place_cursor();
+    }
+
+    fn clear_row(&mut self, row: usize) {
+        self.hide_cursor();
+        self.blank_row(row);
+        self.place_cursor();
+    }
+
+    fn clear(&mut self) {
+        self.hide_cursor();
+        for row in 0..self.rows {
+            self.blank_row(row);
+        }
+        self.col = 0;
+        self.row = 0;
+        self.place_cursor();
+    }
+
+    fn show_cursor(&mut self, visible: bool) {
+        self.hide_cursor();
+        self.cursor_visible = visible;
+        self.place_cursor();
+    }
+}
@@ -0,0 +1,401 @@
+// kernel/src/gdb.rs - GDB 원격 시리얼 프로토콜(RSP) 스텁 (COM2)
+//
+// GDB가 연결되어 있거나 셸의 gdb 명령, 스텁이 넣은 브레이크포인트, 단일 스텝으로
+// 트랩이 발생하면 COM2에서 GDB 명령을 받아 처리하고, c/s 명령을 받으면 중단된
+// 코드로 돌아감. 그 밖의 int3는 커널이 프레임만 출력함. 스텁 안에서는 인터럽트가 꺼져 있음.
+use uart_16550::SerialPort;
+use spin::Mutex;
+use core::sync::atomic::{AtomicBool, Ordering};
+use lazy_static::lazy_static;
+use x86_64::registers::control::{Cr0, Cr0Flags};
+use crate::crashdump;
+use crate::interrupts::TrapFrame;
+
+pub const COM2: u16 = 0x2F8;
+
+const PACKET_SIZE: usize = 1024;
+const MAX_BREAKPOINTS: usize = 16;
+const INT3: u8 = 0xCC;
+const RFLAGS_TF: u64 = 1 << 8; // 트랩 플래그 (명령 하나 실행 후 #DB)
+
+// 'g' 패킷: rax..r15, rip (8바이트) + eflags, cs, ss, ds, es, fs, gs (4바이트)
+const GPR_COUNT: usize = 17;
+const SEGMENT_COUNT: usize = 7;
+
+const SIGTRAP: &str = "S05";
+
+lazy_static! {
+    static ref SERIAL2: Mutex<SerialPort> = {
+        let mut serial_port = unsafe { SerialPort::new(COM2) };
+        serial_port.init();
+        Mutex::new(serial_port)
+    };
+}
+
+// GDB와 패킷을 주고받은 적이 있는지 (D로 분리하면 해제)
+static ATTACHED: AtomicBool = AtomicBool::new(false);
+
+pub fn is_attached() -> bool {
+    ATTACHED.load(Ordering::SeqCst)
+}
+
+// 셸의 gdb 명령 (int3로 스텁에 들어가도록 표시)
+static BREAK_REQUESTED: AtomicBool = AtomicBool::new(false);
+// s 명령 후 다음 #DB는 스텁 몫
+static STEPPING: AtomicBool = AtomicBool::new(false);
+
+pub fn break_in() {
+    BREAK_REQUESTED.store(true, Ordering::SeqCst);
+    x86_64::instructions::interrupts::int3();
+}
+
+// 이 트랩을 스텁이 처리해야 하는지 (아니면 커널이 프레임만 출력하고 계속)
+pub fn wants_trap(frame: &TrapFrame) -> bool {
+    let requested = BREAK_REQUESTED.swap(false, Ordering::SeqCst);
+    let stepping = frame.vector == 1 && STEPPING.swap(false, Ordering::SeqCst);
+    let stub_breakpoint = frame.vector == 3 && is_breakpoint(frame.rip.wrapping_sub(1));
+    is_attached() || requested || stepping || stub_breakpoint
+}
+
+#[derive(Clone, Copy)]
+struct Breakpoint {
+    addr: u64,
+    saved: u8, // int3로 덮어쓴 원래 바이트
+}
+
+static BREAKPOINTS: Mutex<[Option<Breakpoint>; MAX_BREAKPOINTS]> = Mutex::new([None; MAX_BREAKPOINTS]);
+
+// 고정 크기 패킷 버퍼
+struct Packet {
+    buf: [u8; PACKET_SIZE],
+    len: usize,
+}
+
+impl Packet {
+    const fn new() -> Self {
+        Packet { buf: [0; PACKET_SIZE], len: 0 }
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    fn push(&mut self, byte: u8) {
+        if self.len < PACKET_SIZE {
+            self.buf[self.len] = byte;
+            self.len += 1;
+        }
+    }
+
+    fn push_str(&mut self, s: &str) {
+        for &byte in s.as_bytes() {
+            self.push(byte);
+        }
+    }
+
+    fn push_hex(&mut self, byte: u8) {
+        const HEX: &[u8] = b"0123456789abcdef";
+        self.push(HEX[(byte >> 4) as usize]);
+        self.push(HEX[(byte & 0xF) as usize]);
+    }
+
+    // 리틀 엔디언 바이트 순서로 size 바이트
+    fn push_le(&mut self, value: u64, size: usize) {
+        for i in 0..size {
+            self.push_hex((value >> (i * 8)) as u8);
+        }
+    }
+}
+
+fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+fn parse_hex(s: &[u8]) -> Option<u64> {
+    if s.is_empty() || s.len() > 16 {
+        return None;
+    }
+    s.iter().try_fold(0u64, |acc, &c| Some((acc << 4) | hex_value(c)? as u64))
+}
+
+fn hex_byte(s: &[u8]) -> Option<u8> {
+    Some(hex_value(*s.first()?)? << 4 | hex_value(*s.get(1)?)?)
+}
+
+fn parse_le(s: &[u8], size: usize) -> Option<u64> {
+    (0..size).try_fold(0u64, |acc, i| {
+        Some(acc | (hex_byte(s.get(i * 2..i * 2 + 2)?)? as u64) << (i * 8))
+    })
+}
+
+// "addr,len" 형식
+fn parse_addr_len(s: &[u8]) -> Option<(u64, u64)> {
+    let comma = s.iter().position(|&c| c == b',')?;
+    Some((parse_hex(&s[..comma])?, parse_hex(&s[comma + 1..])?))
+}
+
+// 범위가 걸친 페이지를 하나씩 폴트에 안전하게 읽어 봄 (매핑되지 않은 주소는 E14)
+// 널 페이지와 비정규 주소는 probe_byte가 거름
+fn valid_range(addr: u64, len: u64) -> bool {
+    let last = match addr.checked_add(len) {
+        Some(end) => end.saturating_sub(1).max(addr),
+        None => return false,
+    };
+    let mut page = addr;
+    loop {
+        if crashdump::probe_byte(page).is_none() {
+            return false;
+        }
+        page = (page & !0xFFF) + 0x1000;
+        if page > last {
+            return true;
+        }
+    }
+}
+
+// 코드 영역(읽기 전용 페이지)에도 쓸 수 있도록 CR0.WP를 잠시 끔
+fn write_byte(addr: u64, value: u8) {
+    unsafe {
+        let cr0 = Cr0::read();
+        Cr0::write(cr0 - Cr0Flags::WRITE_PROTECT);
+        core::ptr::write_volatile(addr as *mut u8, value);
+        Cr0::write(cr0);
+    }
+}
+
+// valid_range로 확인한 주소만
+fn read_byte(addr: u64) -> u8 {
+    crashdump::probe_byte(addr).unwrap_or(0)
+}
+
+fn registers(frame: &TrapFrame) -> [u64; GPR_COUNT] {
+    [
+        frame.rax, frame.rbx, frame.rcx, frame.rdx, frame.rsi, frame.rdi, frame.rbp, frame.rsp,
+        frame.r8, frame.r9, frame.r10, frame.r11, frame.r12, frame.r13, frame.r14, frame.r15,
+        frame.rip,
+    ]
+}
+
+fn set_registers(frame: &mut TrapFrame, regs: &[u64; GPR_COUNT]) {
+    frame.rax = regs[0];
+    frame.rbx = regs[1];
+    frame.rcx = regs[2];
+    frame.rdx = regs[3];
+    frame.rsi = regs[4];
+    frame.rdi = regs[5];
+    frame.rbp = regs[6];
+    frame.rsp = regs[7];
+    frame.r8 = regs[8];
+    frame.r9 = regs[9];
+    frame.r10 = regs[10];
+    frame.r11 = regs[11];
+    frame.r12 = regs[12];
+    frame.r13 = regs[13];
+    frame.r14 = regs[14];
+    frame.r15 = regs[15];
+    frame.rip = regs[16];
+}
+
+fn checksum(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |sum, &c| sum.wrapping_add(c))
+}
+
+// '$' ... '#' 체크섬 -> 맞으면 '+', 틀리면 '-' 후 다시 받음
+fn receive_packet(port: &mut SerialPort, packet: &mut Packet) {
+    loop {
+        while port.receive() != b'$' {}
+
+        packet.len = 0;
+        let mut byte = port.receive();
+        while byte != b'#' {
+            packet.push(byte);
+            byte = port.receive();
+        }
+        let expected = hex_byte(&[port.receive(), port.receive()]);
+
+        if expected == Some(checksum(packet.as_bytes())) {
+            port.send_raw(b'+');
+            return;
+        }
+        port.send_raw(b'-');
+    }
+}
+
+// GDB가 '+'로 확인할 때까지 재전송
+fn send_packet(port: &mut SerialPort, data: &[u8]) {
+    let sum = checksum(data);
+    let mut trailer = Packet::new();
+    trailer.push(b'#');
+    trailer.push_hex(sum);
+
+    loop {
+        port.send_raw(b'$');
+        for &byte in data.iter().chain(trailer.as_bytes()) {
+            port.send_raw(byte);
+        }
+        match port.receive() {
+            b'+' => return,
+            b'-' => continue,
+            _ => return, // 연결이 끊긴 경우 무한 재전송하지 않음
+        }
+    }
+}
+
+fn insert_breakpoint(addr: u64) -> bool {
+    let mut breakpoints = BREAKPOINTS.lock();
+    if breakpoints.iter().flatten().any(|bp| bp.addr == addr) {
+        return true;
+    }
+    match breakpoints.iter_mut().find(|slot| slot.is_none()) {
+        Some(slot) => {
+            *slot = Some(Breakpoint { addr, saved: read_byte(addr) });
+            write_byte(addr, INT3);
+            true
+        },
+        None => false,
+    }
+}
+
+fn remove_breakpoint(addr: u64) -> bool {
+    let mut breakpoints = BREAKPOINTS.lock();
+    match breakpoints.iter_mut().find(|slot| matches!(slot, Some(bp) if bp.addr == addr)) {
+        Some(slot) => {
+            if let Some(bp) = slot.take() {
+                write_byte(bp.addr, bp.saved);
+            }
+            true
+        },
+        None => false,
+    }
+}
+
+fn is_breakpoint(addr: u64) -> bool {
+    BREAKPOINTS.lock().iter().flatten().any(|bp| bp.addr == addr)
+}
+
+// #BP / #DB 에서 호출. GDB가 c 또는 s를 보낼 때까지 반환하지 않음
+pub fn handle_trap(frame: &mut TrapFrame) {
+    // 단일 스텝은 한 번만
+    frame.rflags &= !RFLAGS_TF;
+
+    // 스텁이 넣은 int3면 원래 명령을 다시 실행하도록 RIP를 되돌림
+    if frame.vector == 3 && is_breakpoint(frame.rip.wrapping_sub(1)) {
+        frame.rip -= 1;
+    }
+
+    let mut port = SERIAL2.lock();
+    let mut request = Packet::new();
+    let mut reply = Packet::new();
+
+    // 처음 연결될 때는 GDB가 '?'로 중단 이유를 물어봄
+    if is_attached() {
+        send_packet(&mut port, SIGTRAP.as_bytes());
+    }
+
+    loop {
+        receive_packet(&mut port, &mut request);
+        ATTACHED.store(true, Ordering::SeqCst);
+        reply.len = 0;
+
+        let data = request.as_bytes();
+        let (&command, args) = match data.split_first() {
+            Some(split) => split,
+            None => {
+                send_packet(&mut port, b"");
+                continue;
+            },
+        };
+
+        match command {
+            b'?' => reply.push_str(SIGTRAP),
+            b'g' => {
+                for reg in registers(frame) {
+                    reply.push_le(reg, 8);
+                }
+                let segments: [u64; SEGMENT_COUNT] = [frame.rflags, frame.cs, frame.ss, 0, 0, 0, 0];
+                for seg in segments {
+                    reply.push_le(seg, 4);
+                }
+            },
+            b'G' => {
+                let mut regs = [0u64; GPR_COUNT];
+                let parsed = (0..GPR_COUNT).all(|i| {
+                    match args.get(i * 16..i * 16 + 16).and_then(|s| parse_le(s, 8)) {
+                        Some(value) => { regs[i] = value; true },
+                        None => false,
+                    }
+                });
+                let rflags = args.get(GPR_COUNT * 16..GPR_COUNT * 16 + 8).and_then(|s| parse_le(s, 4));
+                match (parsed, rflags) {
+                    (true, Some(rflags)) => {
+                        set_registers(frame, &regs);
+                        frame.rflags = rflags;
+                        reply.push_str("OK");
+                    },
+                    _ => reply.push_str("E01"),
+                }
+            },
+            b'm' => match parse_addr_len(args) {
+                Some((addr, len)) if len <= (PACKET_SIZE / 2) as u64 && valid_range(addr, len) => {
+                    for offset in 0..len {
+                        reply.push_hex(read_byte(addr + offset));
+                    }
+                },
+                Some(_) => reply.push_str("E14"),
+                None => reply.push_str("E01"),
+            },
+            b'M' => {
+                let colon = args.iter().position(|&c| c == b':');
+                match colon.and_then(|i| Some((parse_addr_len(&args[..i])?, &args[i + 1..]))) {
+                    Some(((addr, len), bytes)) if valid_range(addr, len) && bytes.len() as u64 >= len * 2 => {
+                        for offset in 0..len as usize {
+                            if let Some(value) = hex_byte(&bytes[offset * 2..]) {
+                                write_byte(addr + offset as u64, value);
+                            }
+                        }
+                        reply.push_str("OK");
+                    },
+                    Some(_) => reply.push_str("E14"),
+                    None => reply.push_str("E01"),
+                }
+            },
+            b'c' | b's' => {
+                if let Some(addr) = parse_hex(args) {
+                    frame.rip = addr;
+                }
+                if command == b's' {
+                    frame.rflags |= RFLAGS_TF;
+                    STEPPING.store(true, Ordering::SeqCst);
+                }
+                return;
+            },
+            b'Z' | b'z' if args.starts_with(b"0,") => {
+                // Z0,addr,kind - 소프트웨어 브레이크포인트
+                let ok = match parse_addr_len(&args[2..]) {
+                    Some((addr, _)) if valid_range(addr, 1) => {
+                        if command == b'Z' { insert_breakpoint(addr) } else { remove_breakpoint(addr) }
+                    },
+                    _ => false,
+                };
+                reply.push_str(if ok { "OK" } else { "E01" });
+            },
+            b'D' => {
+                ATTACHED.store(false, Ordering::SeqCst);
+                send_packet(&mut port, b"OK");
+                return;
+            },
+            b'k' => return, // 커널은 종료할 수 없으므로 계속 실행
+            b'H' => reply.push_str("OK"),
+            b'q' if args.starts_with(b"Supported") => reply.push_str("PacketSize=400"),
+            b'q' if args.starts_with(b"Attached") => reply.push_str("1"),
+            _ => {}, // 지원하지 않는 명령은 빈 응답
+        }
+
+        send_packet(&mut port, reply.as_bytes());
+    }
+}
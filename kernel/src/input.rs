@@ -0,0 +1,166 @@
+// kernel/src/input.rs - 입력 이벤트 큐 (키보드, 마우스, 시리얼, 향후 USB HID 공용)
+use spin::Mutex;
+use core::sync::atomic::{AtomicBool, Ordering};
+use x86_64::instructions::interrupts;
+use crate::keyboard::KeyEvent;
+use crate::mouse::MouseEvent;
+
+const QUEUE_SIZE: usize = 256;
+const MAX_SUBSCRIBERS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputDevice {
+    Ps2Keyboard,
+    Ps2Mouse,
+    Com1,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputEventKind {
+    Key(KeyEvent),
+    Mouse(MouseEvent),
+    Serial(u8), // 시리얼 콘솔에서 받은 바이트 그대로
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputEvent {
+    pub timestamp: u64, // 타이머 틱 (100Hz)
+    pub device: InputDevice,
+    pub kind: InputEventKind,
+}
+
+#[derive(Clone, Copy)]
+struct SubscriberSlot {
+    read_seq: u64,  // 다음에 읽을 이벤트 번호
+    dropped: u64,   // 읽기 전에 덮어써진 이벤트 수
+}
+
+// 모든 구독자가 같은 링 버퍼를 각자의 위치에서 읽음.
+// 느린 구독자 때문에 쓰기가 막히지 않도록 가장 오래된 이벤트를 덮어쓰고 개수를 셈.
+struct InputQueue {
+    events: [Option<InputEvent>; QUEUE_SIZE],
+    write_seq: u64,
+    subscribers: [Option<SubscriberSlot>; MAX_SUBSCRIBERS],
+    overflows: u64,
+}
+
+impl InputQueue {
+    const fn new() -> Self {
+        InputQueue {
+            events: [None; QUEUE_SIZE],
+            write_seq: 0,
+            subscribers: [None; MAX_SUBSCRIBERS],
+            overflows: 0,
+        }
+    }
+
+    fn push(&mut self, event: InputEvent) {
+        for slot in self.subscribers.iter_mut().flatten() {
+            if self.write_seq - slot.read_seq >= QUEUE_SIZE as u64 {
+                slot.read_seq += 1;
+                slot.dropped += 1;
+                self.overflows += 1;
+            }
+        }
+        self.events[(self.write_seq % QUEUE_SIZE as u64) as usize] = Some(event);
+        self.write_seq += 1;
+    }
+
+    fn pop(&mut self, id: usize) -> Option<InputEvent> {
+        let slot = self.subscribers[id].as_mut()?;
+        if slot.read_seq == self.write_seq {
+            return None;
+        }
+        let event = self.events[(slot.read_seq % QUEUE_SIZE as u64) as usize];
+        slot.read_seq += 1;
+        event
+    }
+}
+
+static QUEUE: Mutex<InputQueue> = Mutex::new(InputQueue::new());
+
+// Ctrl+C (키보드, 시리얼 모두) -> 포그라운드 명령 중단 요청
+static INTERRUPT_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+// 드라이버(인터럽트 핸들러)에서 호출
+pub fn push(device: InputDevice, kind: InputEventKind) {
+    let timestamp = crate::interrupts::get_timer_ticks();
+    interrupts::without_interrupts(|| {
+        QUEUE.lock().push(InputEvent { timestamp, device, kind });
+    });
+}
+
+pub fn request_interrupt() {
+    INTERRUPT_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+// 중단 요청이 있었으면 true (확인하면서 지움)
+pub fn take_interrupt() -> bool {
+    INTERRUPT_REQUESTED.swap(false, Ordering::SeqCst)
+}
+
+// 구독 시점 이후의 이벤트만 받음. 슬롯이 없으면 None
+pub fn subscribe() -> Option<Subscriber> {
+    interrupts::without_interrupts(|| {
+        let mut queue = QUEUE.lock();
+        let write_seq = queue.write_seq;
+        let id = queue.subscribers.iter().position(|slot| slot.is_none())?;
+        queue.subscribers[id] = Some(SubscriberSlot { read_seq: write_seq, dropped: 0 });
+        Some(Subscriber { id })
+    })
+}
+
+pub struct Subscriber {
+    id: usize,
+}
+
+impl Subscriber {
+    // 논블로킹 읽기
+    pub fn poll(&self) -> Option<InputEvent> {
+        interrupts::without_interrupts(|| QUEUE.lock().pop(self.id))
+    }
+
+    // 이벤트가 올 때까지 hlt로 대기
+    pub fn wait(&self) -> InputEvent {
+        loop {
+            // 확인과 hlt 사이에 인터럽트를 놓치지 않도록 끈 상태에서 확인
+            interrupts::disable();
+            if let Some(event) = QUEUE.lock().pop(self.id) {
+                interrupts::enable();
+                return event;
+            }
+            interrupts::enable_and_hlt();
+        }
+    }
+
+    pub fn dropped(&self) -> u64 {
+        interrupts::without_interrupts(|| {
+            QUEUE.lock().subscribers[self.id].map_or(0, |slot| slot.dropped)
+        })
+    }
+}
+
+impl Drop for Subscriber {
+    fn drop(&mut self) {
+        interrupts::without_interrupts(|| {
+            QUEUE.lock().subscribers[self.id] = None;
+        });
+    }
+}
+
+pub struct InputStats {
+    pub events: u64,
+    pub overflows: u64,
+    pub subscribers: usize,
+}
+
+pub fn stats() -> InputStats {
+    interrupts::without_interrupts(|| {
+        let queue = QUEUE.lock();
+        InputStats {
+            events: queue.write_seq,
+            overflows: queue.overflows,
+            subscribers: queue.subscribers.iter().flatten().count(),
+        }
+    })
+}
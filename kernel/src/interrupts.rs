@@ -5,62 +5,17 @@ use x86_64::VirtAddr;
 use lazy_static::lazy_static;
 use pic8259::ChainedPics;
 use spin::Mutex;
-use x86_64::instructions::interrupts;
 use x86_64::instructions::hlt;
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::arch::global_asm;
+use log::{info, warn};
 
-pub struct ScancodeBuffer {
-    buffer: [u8; 16],
-    head: usize,
-    tail: usize,
-}
-
-impl ScancodeBuffer {
-    const fn new() -> Self {
-        ScancodeBuffer {
-            buffer: [0; 16],
-            head: 0,
-            tail: 0,
-        }
-    }
-
-    pub fn push(&mut self, scancode: u8) -> bool {
-        let next_tail = (self.tail + 1) % 16;
-        if next_tail != self.head {
-            self.buffer[self.tail] = scancode;
-            self.tail = next_tail;
-            true
-        } else {
-            false
-        }
-    }
-
-	#[allow(dead_code)]
-    pub fn pop(&mut self) -> Option<u8> {
-        if self.head != self.tail {
-            let scancode = self.buffer[self.head];
-            self.head = (self.head + 1) % 16;
-            Some(scancode)
-        } else {
-            None
-        }
-    }
-}
-
-static SCANCODE_BUFFER: Mutex<ScancodeBuffer> = Mutex::new(ScancodeBuffer::new());
-
-#[allow(dead_code)]
-static TIMER_TICKS: Mutex<u64> = Mutex::new(0);
+static TIMER_TICKS: AtomicU64 = AtomicU64::new(0);
 
 static KEYBOARD_INTERRUPTS: Mutex<u64> = Mutex::new(0);
 
-pub fn read_scancode() -> Option<u8> {
-    interrupts::without_interrupts(|| {
-        SCANCODE_BUFFER.lock().pop()
-    })
-}
-
 pub fn get_timer_ticks() -> u64 {
-    *TIMER_TICKS.lock()
+    TIMER_TICKS.load(Ordering::Relaxed)
 }
 
 pub fn get_keyboard_interrupts() -> u64 {
@@ -79,6 +34,8 @@ pub static PICS: Mutex<ChainedPics> =
 pub enum InterruptIndex {
     Timer = PIC_1_OFFSET,
     Keyboard = PIC_1_OFFSET + 1,
+    Serial1 = PIC_1_OFFSET + 4, // IRQ4 (COM1)
+    Mouse = PIC_2_OFFSET + 4, // IRQ12
 }
 
 lazy_static! {
@@ -123,18 +80,22 @@ lazy_static! {
     static ref IDT: InterruptDescriptorTable = {
         let mut idt = InterruptDescriptorTable::new();
         
-        // CPU 예외 핸들러 - 간단한 버전만
-        idt.breakpoint.set_handler_fn(breakpoint_handler);
-        
+        // CPU 예외 핸들러 - 디버거와 크래시 덤프가 레지스터 전체를 보도록 어셈블리 진입점 사용
         unsafe {
+            idt.debug.set_handler_addr(VirtAddr::new(debug_entry as *const () as u64));
+            idt.breakpoint.set_handler_addr(VirtAddr::new(breakpoint_entry as *const () as u64));
+            
             idt.double_fault
-                .set_handler_fn(double_fault_handler)
+                .set_handler_addr(VirtAddr::new(double_fault_entry as *const () as u64))
                 .set_stack_index(DOUBLE_FAULT_IST_INDEX);
+            idt.page_fault.set_handler_addr(VirtAddr::new(page_fault_entry as *const () as u64));
         }
         
         // 하드웨어 인터럽트만 등록
         idt[InterruptIndex::Timer as usize].set_handler_fn(timer_interrupt_handler);
         idt[InterruptIndex::Keyboard as usize].set_handler_fn(keyboard_interrupt_handler);
+        idt[InterruptIndex::Serial1 as usize].set_handler_fn(serial_interrupt_handler);
+        idt[InterruptIndex::Mouse as usize].set_handler_fn(mouse_interrupt_handler);
         
         idt
     };
@@ -147,7 +108,22 @@ pub fn init_idt() {
 pub fn init_pics() {
     unsafe {
         PICS.lock().initialize();  // 추가!
-        PICS.lock().write_masks(0xFC, 0xFF);  // 0b11111100
+        // 마스터: 타이머, 키보드, 슬레이브 연결(IRQ2), COM1(IRQ4) / 슬레이브: 마우스(IRQ12)
+        PICS.lock().write_masks(0xE8, 0xEF);
+    }
+}
+
+// PIT 채널 0을 100Hz로 설정
+pub fn init_pit() {
+    use x86_64::instructions::port::Port;
+    unsafe {
+        let divisor: u16 = 11932; // 100Hz
+        let mut command_port = Port::<u8>::new(0x43);
+        let mut data_port = Port::<u8>::new(0x40);
+        
+        command_port.write(0x36);
+        data_port.write((divisor & 0xFF) as u8);
+        data_port.write((divisor >> 8) as u8);
     }
 }
 
@@ -161,11 +137,16 @@ pub fn are_interrupts_enabled() -> bool {
 }
 
 // 타이머 인터럽트 핸들러
-extern "x86-interrupt" fn timer_interrupt_handler(_stack_frame: InterruptStackFrame) {
+extern "x86-interrupt" fn timer_interrupt_handler(stack_frame: InterruptStackFrame) {
     extern "C" {
         fn increment_tick();
     }
     unsafe { increment_tick(); }
+    TIMER_TICKS.fetch_add(1, Ordering::Relaxed);
+    
+    if crate::profiler::is_running() {
+        crate::profiler::record(stack_frame.instruction_pointer.as_u64());
+    }
     
     unsafe {
         PICS.lock().notify_end_of_interrupt(InterruptIndex::Timer as u8);
@@ -176,9 +157,10 @@ extern "x86-interrupt" fn timer_interrupt_handler(_stack_frame: InterruptStackFr
 extern "x86-interrupt" fn keyboard_interrupt_handler(_stack_frame: InterruptStackFrame) {
     use x86_64::instructions::port::Port;
     
-	let vga = 0xb8000 as *mut u16;
-    unsafe {
-        *vga.add(79) = (b'K' as u16) | (0x4E << 8);  // 오른쪽 상단에 'K'
+    // 오른쪽 상단에 'K' (화면을 쓰는 중이면 생략)
+    if let Some(mut vga) = crate::console::active_terminal().try_lock() {
+        let (width, _) = vga.size();
+        vga.write_cell(width - 1, 0, b'K', 0x4E);
     }
 
     *KEYBOARD_INTERRUPTS.lock() += 1;
@@ -186,32 +168,216 @@ extern "x86-interrupt" fn keyboard_interrupt_handler(_stack_frame: InterruptStac
     let mut port = Port::<u8>::new(0x60);
     let scancode: u8 = unsafe { port.read() };
     
-    SCANCODE_BUFFER.lock().push(scancode);
+    crate::keyboard::handle_scancode(scancode);
     
     unsafe {
         PICS.lock().notify_end_of_interrupt(InterruptIndex::Keyboard as u8);
     }
 }
 
-// 브레이크포인트 핸들러
-extern "x86-interrupt" fn breakpoint_handler(_stack_frame: InterruptStackFrame) {
-    // 아무것도 하지 않음
+// 시리얼(COM1) 수신 인터럽트 핸들러
+extern "x86-interrupt" fn serial_interrupt_handler(_stack_frame: InterruptStackFrame) {
+    crate::serial::handle_interrupt();
+    
+    unsafe {
+        PICS.lock().notify_end_of_interrupt(InterruptIndex::Serial1 as u8);
+    }
 }
 
-// 더블 폴트 핸들러
-extern "x86-interrupt" fn double_fault_handler(
-    _stack_frame: InterruptStackFrame,
-    _error_code: u64,
-) -> ! {
-    // VGA에 직접 에러 메시지 출력
-    let vga = 0xb8000 as *mut u16;
-    let msg = b"DOUBLE FAULT!";
-    for (i, &byte) in msg.iter().enumerate() {
-        unsafe {
-            *vga.add(i) = (byte as u16) | (0x4F << 8); // 빨간 배경
+// 마우스 인터럽트 핸들러
+extern "x86-interrupt" fn mouse_interrupt_handler(_stack_frame: InterruptStackFrame) {
+    crate::mouse::handle_interrupt();
+    
+    unsafe {
+        PICS.lock().notify_end_of_interrupt(InterruptIndex::Mouse as u8);
+    }
+}
+
+// 트랩 진입점이 스택에 저장한 레지스터 (낮은 주소부터)
+#[derive(Debug, Default)]
+#[repr(C)]
+pub struct TrapFrame {
+    pub r15: u64,
+    pub r14: u64,
+    pub r13: u64,
+    pub r12: u64,
+    pub r11: u64,
+    pub r10: u64,
+    pub r9: u64,
+    pub r8: u64,
+    pub rbp: u64,
+    pub rdi: u64,
+    pub rsi: u64,
+    pub rdx: u64,
+    pub rcx: u64,
+    pub rbx: u64,
+    pub rax: u64,
+    pub vector: u64,
+    pub error_code: u64, // 오류 코드가 없는 예외는 진입점이 0을 넣음
+    // CPU가 쌓은 인터럽트 프레임
+    pub rip: u64,
+    pub cs: u64,
+    pub rflags: u64,
+    pub rsp: u64,
+    pub ss: u64,
+}
+
+extern "C" {
+    fn debug_entry();
+    fn breakpoint_entry();
+    fn double_fault_entry();
+    fn page_fault_entry();
+}
+
+// 범용 레지스터와 SSE 상태를 저장하고 trap_handler(&mut TrapFrame) 호출.
+// 64비트 모드에서 CPU는 프레임을 쌓기 전에 RSP를 16바이트 정렬하므로
+// 오류 코드, 벡터, 레지스터 15개를 쌓은 뒤 512바이트를 빼면 fxsave 영역과 call이 정렬됨.
+global_asm!(
+    ".global debug_entry",
+    "debug_entry:",
+    "    push 0",
+    "    push 1",
+    "    jmp trap_common",
+    ".global breakpoint_entry",
+    "breakpoint_entry:",
+    "    push 0",
+    "    push 3",
+    "    jmp trap_common",
+    // #DF, #PF는 CPU가 오류 코드를 쌓음
+    ".global double_fault_entry",
+    "double_fault_entry:",
+    "    push 8",
+    "    jmp trap_common",
+    ".global page_fault_entry",
+    "page_fault_entry:",
+    "    push 14",
+    "    jmp trap_common",
+    "trap_common:",
+    "    push rax",
+    "    push rbx",
+    "    push rcx",
+    "    push rdx",
+    "    push rsi",
+    "    push rdi",
+    "    push rbp",
+    "    push r8",
+    "    push r9",
+    "    push r10",
+    "    push r11",
+    "    push r12",
+    "    push r13",
+    "    push r14",
+    "    push r15",
+    "    mov rdi, rsp",
+    "    sub rsp, 512",
+    "    fxsave64 [rsp]",
+    "    cld",
+    "    call {handler}",
+    "    fxrstor64 [rsp]",
+    "    add rsp, 512",
+    "    pop r15",
+    "    pop r14",
+    "    pop r13",
+    "    pop r12",
+    "    pop r11",
+    "    pop r10",
+    "    pop r9",
+    "    pop r8",
+    "    pop rbp",
+    "    pop rdi",
+    "    pop rsi",
+    "    pop rdx",
+    "    pop rcx",
+    "    pop rbx",
+    "    pop rax",
+    "    add rsp, 16",
+    "    iretq",
+    handler = sym trap_handler,
+);
+
+extern "C" fn trap_handler(frame: &mut TrapFrame) {
+    match frame.vector {
+        1 => debug_handler(frame),
+        8 => double_fault_handler(frame),
+        14 => page_fault_handler(frame),
+        _ => breakpoint_handler(frame),
+    }
+}
+
+const RFLAGS_RF: u64 = 1 << 16; // 재개 플래그 (실행 브레이크포인트 한 번 건너뜀)
+
+// 디버그 예외: 워치포인트, 단일 스텝
+fn debug_handler(frame: &mut TrapFrame) {
+    let status = crate::watchpoint::take_status();
+    let mut hit = false;
+    for slot in crate::watchpoint::triggered(status) {
+        // 꺼진 슬롯도 조건이 맞으면 DR6에 표시될 수 있음
+        if let Some(w) = crate::watchpoint::get(slot) {
+            hit = true;
+            crate::watchpoint::record_hit(slot);
+            warn!("watchpoint {} ({} {} bytes at {:#x}) hit", slot, w.kind.as_str(), w.len, w.addr);
         }
     }
+    if crate::watchpoint::is_execute(status) {
+        frame.rflags |= RFLAGS_RF;
+    }
+
+    if crate::gdb::wants_trap(frame) {
+        crate::gdb::handle_trap(frame);
+    } else {
+        log_frame(if hit { "#DB (watchpoint)" } else { "#DB" }, frame);
+    }
+}
+
+// 브레이크포인트 (int3): GDB가 연결되어 있으면 스텁으로, 아니면 프레임을 출력하고 계속
+fn breakpoint_handler(frame: &mut TrapFrame) {
+    if crate::gdb::wants_trap(frame) {
+        crate::gdb::handle_trap(frame);
+    } else {
+        // RIP는 이미 int3 다음 명령을 가리킴
+        log_frame("int3", frame);
+    }
+}
+
+// 트랩 프레임을 커널 로그로 출력 (dmesg, 시리얼)
+fn log_frame(what: &str, frame: &TrapFrame) {
+    match crate::ksyms::lookup(frame.rip) {
+        Some(sym) => warn!("{} at {:#x} ({}+{:#x})", what, frame.rip, sym.name, frame.rip - sym.addr),
+        None => warn!("{} at {:#x}", what, frame.rip),
+    }
+    let rows = [
+        [("rax", frame.rax), ("rbx", frame.rbx), ("rcx", frame.rcx)],
+        [("rdx", frame.rdx), ("rsi", frame.rsi), ("rdi", frame.rdi)],
+        [("rbp", frame.rbp), ("rsp", frame.rsp), ("r8", frame.r8)],
+        [("r9", frame.r9), ("r10", frame.r10), ("r11", frame.r11)],
+        [("r12", frame.r12), ("r13", frame.r13), ("r14", frame.r14)],
+    ];
+    for [(n1, v1), (n2, v2), (n3, v3)] in rows {
+        info!("  {:<3}={:016x} {:<3}={:016x} {:<3}={:016x}", n1, v1, n2, v2, n3, v3);
+    }
+    info!("  r15={:016x} rflags={:08x} cs={:04x} ss={:04x}", frame.r15, frame.rflags, frame.cs, frame.ss);
+}
+
+// 페이지 폴트 -> 폴트에 안전한 메모리 읽기(크래시 덤프, GDB 스텁)가 아니면 크래시 기록 후 정지
+fn page_fault_handler(frame: &mut TrapFrame) {
+    if crate::crashdump::fixup(frame) {
+        return;
+    }
+    let addr = x86_64::registers::control::Cr2::read().as_u64();
+    crate::crashdump::dump("page-fault",
+        format_args!("address {:#x} error {:#x} at {:#x}", addr, frame.error_code, frame.rip), frame);
+    halt_with_message("PAGE FAULT!", format_args!("address {:#x} at {:#x}", addr, frame.rip));
+}
+
+// 더블 폴트 핸들러 (IST 스택에서 실행)
+fn double_fault_handler(frame: &mut TrapFrame) -> ! {
+    crate::crashdump::dump("double-fault", format_args!("at {:#x}", frame.rip), frame);
+    halt_with_message("DOUBLE FAULT!", format_args!("at {:#x}", frame.rip));
+}
+
+fn halt_with_message(title: &str, detail: core::fmt::Arguments) -> ! {
+    crate::console::crash_screen(title, detail);
     loop {
         hlt();
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,150 @@
+// kernel/src/keyboard.rs - PS/2 키보드 드라이버 (스캔코드 세트 1)
+use spin::Mutex;
+use x86_64::instructions::interrupts;
+use crate::keymap::Layout;
+use crate::ps2;
+use crate::power;
+use crate::input::{self, InputDevice, InputEventKind};
+
+// 스캔코드 변환은 kernel_core에 있음
+pub use kernel_core::scancode::*;
+
+static KEYBOARD: Mutex<Keyboard> = Mutex::new(Keyboard::new());
+static LEDS: Mutex<LedState> = Mutex::new(LedState::new());
+
+const LED_COMMAND: u8 = 0xED;
+const LED_MAX_RESENDS: u8 = 3;
+const LED_TIMEOUT_TICKS: u64 = 10; // 100ms 안에 ACK가 없으면 처음부터 다시 보냄
+
+// LED 명령 진행 단계. 키보드의 ACK는 IRQ1으로 들어오므로 기다리지 않고 단계만 기록
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LedStep {
+    Idle,
+    Command, // 0xED를 보내고 ACK 대기
+    Data,    // LED 바이트를 보내고 ACK 대기
+}
+
+struct LedState {
+    step: LedStep,
+    wanted: u8,          // 켜야 할 LED
+    sending: u8,         // 지금 보내는 LED
+    current: Option<u8>, // 키보드가 마지막으로 ACK한 LED
+    resends: u8,
+    started: u64,        // 0xED를 보낸 타이머 틱
+}
+
+impl LedState {
+    const fn new() -> Self {
+        LedState { step: LedStep::Idle, wanted: 0, sending: 0, current: None, resends: 0, started: 0 }
+    }
+
+    fn request(&mut self, leds: u8) {
+        self.wanted = leds & 0x07;
+        let stale = crate::interrupts::get_timer_ticks().wrapping_sub(self.started) > LED_TIMEOUT_TICKS;
+        if self.step == LedStep::Idle || stale {
+            self.start();
+        }
+    }
+
+    fn start(&mut self) {
+        self.step = LedStep::Command;
+        self.sending = self.wanted;
+        self.resends = 0;
+        self.started = crate::interrupts::get_timer_ticks();
+        ps2::write_data(LED_COMMAND);
+    }
+
+    // LED 명령의 응답(ACK, RESEND)이면 처리하고 true
+    fn response(&mut self, byte: u8) -> bool {
+        if self.step == LedStep::Idle {
+            return false;
+        }
+        match byte {
+            ps2::DEVICE_ACK if self.step == LedStep::Command => {
+                self.step = LedStep::Data;
+                self.resends = 0;
+                ps2::write_data(self.sending);
+            },
+            ps2::DEVICE_ACK => {
+                self.step = LedStep::Idle;
+                self.current = Some(self.sending);
+                // 기다리는 동안 바뀐 값이 있으면 이어서 보냄
+                if self.wanted != self.sending {
+                    self.start();
+                }
+            },
+            ps2::DEVICE_RESEND if self.resends < LED_MAX_RESENDS => {
+                self.resends += 1;
+                ps2::write_data(if self.step == LedStep::Command { LED_COMMAND } else { self.sending });
+            },
+            ps2::DEVICE_RESEND => self.step = LedStep::Idle,
+            _ => return false,
+        }
+        true
+    }
+}
+
+pub fn process_scancode(scancode: u8) -> Option<KeyEvent> {
+    interrupts::without_interrupts(|| {
+        let mut keyboard = KEYBOARD.lock();
+        let old_leds = keyboard.modifiers().leds();
+
+        let event = keyboard.process(scancode);
+
+        // LED 값만 넘기고 실제 전송은 IRQ1으로 오는 ACK에 맞춰 진행
+        let new_leds = keyboard.modifiers().leds();
+        if new_leds != old_leds {
+            set_leds(new_leds);
+        }
+        event
+    })
+}
+
+// 키보드 인터럽트 핸들러에서 호출 -> 입력 큐로 전달
+// 셸이 바빠도 동작해야 하는 키 조합은 여기서 바로 처리
+pub fn handle_scancode(scancode: u8) {
+    if interrupts::without_interrupts(|| LEDS.lock().response(scancode)) {
+        return;
+    }
+    if let Some(event) = process_scancode(scancode) {
+        if event.is_pressed() {
+            if event.code == KeyCode::Delete && event.modifiers.ctrl() && event.modifiers.alt() {
+                power::reboot();
+            }
+            if event.ch == Some(CTRL_C) {
+                input::request_interrupt();
+            }
+        }
+        input::push(InputDevice::Ps2Keyboard, InputEventKind::Key(event));
+    }
+}
+
+pub fn modifiers() -> Modifiers {
+    interrupts::without_interrupts(|| KEYBOARD.lock().modifiers())
+}
+
+pub fn layout() -> &'static Layout {
+    interrupts::without_interrupts(|| KEYBOARD.lock().layout())
+}
+
+pub fn set_layout(layout: &'static Layout) {
+    interrupts::without_interrupts(|| KEYBOARD.lock().set_layout(layout))
+}
+
+pub fn hangul_mode() -> bool {
+    interrupts::without_interrupts(|| KEYBOARD.lock().hangul_mode())
+}
+
+pub fn init() {
+    set_leds(modifiers().leds());
+}
+
+// Caps/Num/Scroll Lock LED 설정 (0xED만 보내고, LED 바이트는 ACK가 오면 보냄)
+pub fn set_leds(leds: u8) {
+    interrupts::without_interrupts(|| LEDS.lock().request(leds))
+}
+
+// 키보드가 마지막으로 ACK한 LED (아직 없으면 None)
+pub fn leds() -> Option<u8> {
+    interrupts::without_interrupts(|| LEDS.lock().current)
+}
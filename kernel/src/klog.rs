@@ -0,0 +1,207 @@
+// kernel/src/klog.rs - log 크레이트 백엔드 (dmesg 링 버퍼 + VGA/시리얼/0xE9 출력)
+use core::fmt::Write;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use spin::Mutex;
+use x86_64::instructions::interrupts;
+use x86_64::instructions::port::Port;
+use crate::LineBuf;
+
+const LOG_SIZE: usize = 128;
+const DEBUGCON_PORT: u16 = 0xE9; // QEMU -debugcon
+const CRATE_PREFIX: &str = "myos_kernel::";
+
+// 출력 대상 (링 버퍼에는 항상 저장)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sinks {
+    pub vga: bool,
+    pub serial: bool,
+    pub debugcon: bool,
+}
+
+#[derive(Clone, Copy)]
+pub struct LogEntry {
+    pub timestamp: u64, // 타이머 틱 (100Hz)
+    pub level: Level,
+    pub target: &'static str,
+    text: [u8; 128],
+    len: usize,
+}
+
+impl LogEntry {
+    pub fn message(&self) -> &str {
+        core::str::from_utf8(&self.text[..self.len]).unwrap_or("")
+    }
+
+    // "[   12.34] INFO  ps2: message"
+    pub fn format(&self, line: &mut LineBuf) {
+        let _ = write!(line, "[{:5}.{:02}] {:<5} {}: {}",
+            self.timestamp / 100, self.timestamp % 100, self.level, self.target, self.message());
+    }
+}
+
+struct LogBuffer {
+    entries: [Option<LogEntry>; LOG_SIZE],
+    write_seq: u64,
+    sinks: Sinks,
+}
+
+impl LogBuffer {
+    const fn new() -> Self {
+        LogBuffer {
+            entries: [None; LOG_SIZE],
+            write_seq: 0,
+            sinks: Sinks { vga: false, serial: true, debugcon: true },
+        }
+    }
+
+    fn push(&mut self, entry: LogEntry) {
+        self.entries[(self.write_seq % LOG_SIZE as u64) as usize] = Some(entry);
+        self.write_seq += 1;
+    }
+}
+
+static LOG: Mutex<LogBuffer> = Mutex::new(LogBuffer::new());
+
+// 메시지 길이 제한 (넘치면 잘라냄)
+struct MessageBuf {
+    text: [u8; 128],
+    len: usize,
+}
+
+impl Write for MessageBuf {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for ch in s.chars() {
+            let n = ch.len_utf8();
+            if self.len + n > self.text.len() {
+                break;
+            }
+            ch.encode_utf8(&mut self.text[self.len..]);
+            self.len += n;
+        }
+        Ok(())
+    }
+}
+
+// 모듈 경로에서 크레이트 이름 떼기 (myos_kernel::ps2 -> ps2)
+fn short_target(target: &'static str) -> &'static str {
+    match target.strip_prefix(CRATE_PREFIX) {
+        Some(rest) => rest,
+        None if target == "myos_kernel" => "kernel",
+        None => target,
+    }
+}
+
+pub fn level_color(level: Level) -> u8 {
+    match level {
+        Level::Error => 0x0C,
+        Level::Warn => 0x0E,
+        Level::Info => 0x07,
+        Level::Debug | Level::Trace => 0x08,
+    }
+}
+
+// VGA, 시리얼, 0xE9로 내보내는 최대 수준
+const SINK_LEVEL: LevelFilter = LevelFilter::Debug;
+
+struct KernelLogger;
+
+impl Log for KernelLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let mut message = MessageBuf { text: [0; 128], len: 0 };
+        let _ = message.write_fmt(*record.args());
+        let entry = LogEntry {
+            timestamp: crate::interrupts::get_timer_ticks(),
+            level: record.level(),
+            target: short_target(record.module_path_static().unwrap_or("?")),
+            text: message.text,
+            len: message.len,
+        };
+        let mut line = LineBuf::new();
+        entry.format(&mut line);
+
+        // 인터럽트 핸들러에서도 로그를 남기므로 잠금 중에는 인터럽트 차단
+        interrupts::without_interrupts(|| {
+            let mut log = LOG.lock();
+            log.push(entry);
+            // trace는 링 버퍼(dmesg trace)에만
+            if entry.level > SINK_LEVEL {
+                return;
+            }
+
+            if log.sinks.vga {
+                // 콘솔을 쓰는 중에 인터럽트 핸들러가 남긴 로그는 VGA에서 생략
+                crate::console::try_with(|console| {
+                    console.set_color(level_color(entry.level));
+                    console.write_str(line.as_str());
+                    console.write_str("\n");
+                });
+            }
+            if log.sinks.serial {
+                crate::serial_println!("{}", line.as_str());
+            }
+            if log.sinks.debugcon {
+                let mut port = Port::<u8>::new(DEBUGCON_PORT);
+                for &byte in line.as_str().as_bytes().iter().chain(b"\n") {
+                    unsafe { port.write(byte); }
+                }
+            }
+        });
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: KernelLogger = KernelLogger;
+
+pub fn init(sinks: Sinks) {
+    LOG.lock().sinks = sinks;
+    log::set_logger(&LOGGER).expect("logger already set");
+    log::set_max_level(LevelFilter::Trace);
+}
+
+// 출력 대상 변경 (VGA는 콘솔 커서 위치에 이어서 출력)
+pub fn set_sinks(sinks: Sinks) {
+    interrupts::without_interrupts(|| LOG.lock().sinks = sinks);
+}
+
+// 링 버퍼에 남아 있는 가장 오래된 항목부터 seq 번호로 읽음
+pub fn first_seq() -> u64 {
+    interrupts::without_interrupts(|| LOG.lock().write_seq.saturating_sub(LOG_SIZE as u64))
+}
+
+// 다음에 기록될 항목의 seq (이후 로그만 보려면 먼저 저장)
+pub fn next_seq() -> u64 {
+    interrupts::without_interrupts(|| LOG.lock().write_seq)
+}
+
+pub fn read(seq: u64) -> Option<LogEntry> {
+    interrupts::without_interrupts(|| {
+        let log = LOG.lock();
+        if seq >= log.write_seq || seq + (LOG_SIZE as u64) < log.write_seq {
+            return None;
+        }
+        log.entries[(seq % LOG_SIZE as u64) as usize]
+    })
+}
+
+// 크래시 덤프용: 최근 count개를 오래된 것부터. 잠겨 있으면 기다리지 않고 false
+pub fn try_for_each_recent(count: usize, mut f: impl FnMut(&LogEntry)) -> bool {
+    let Some(log) = LOG.try_lock() else {
+        return false;
+    };
+    let start = log.write_seq.saturating_sub(count.min(LOG_SIZE) as u64);
+    for seq in start..log.write_seq {
+        if let Some(entry) = &log.entries[(seq % LOG_SIZE as u64) as usize] {
+            f(entry);
+        }
+    }
+    true
+}
@@ -0,0 +1,76 @@
+// kernel/src/ksyms.rs - 커널에 내장된 심볼 테이블 (빌드 후 tools/ksyms가 채움)
+//
+// .ksyms 섹션 형식 (리틀 엔디언):
+//   "KSYM" | 개수 u32 | 항목 [주소 u64, 크기 u32, 이름 위치 u32] * 개수 (주소 순) | 이름들
+//   이름은 [길이 u8, 바이트...]
+use core::ptr::addr_of;
+
+pub const KSYMS_SIZE: usize = 256 * 1024;
+const MAGIC: &[u8; 4] = b"KSYM";
+const HEADER_SIZE: usize = 8;
+const ENTRY_SIZE: usize = 16;
+
+#[used]
+#[link_section = ".ksyms"]
+static KSYMS: [u8; KSYMS_SIZE] = [0; KSYMS_SIZE];
+
+fn table() -> &'static [u8] {
+    // 빌드 후에 내용이 바뀌므로 컴파일러가 0으로 가정하지 않도록 함
+    unsafe { &*core::hint::black_box(addr_of!(KSYMS)) }
+}
+
+fn u32_at(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn u64_at(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+fn count() -> usize {
+    let data = table();
+    if &data[..4] != MAGIC {
+        return 0;
+    }
+    (u32_at(data, 4) as usize).min((KSYMS_SIZE - HEADER_SIZE) / ENTRY_SIZE)
+}
+
+pub fn is_loaded() -> bool {
+    count() > 0
+}
+
+pub struct Symbol {
+    pub addr: u64,
+    pub size: u64,
+    pub name: &'static str,
+}
+
+fn entry(index: usize) -> Symbol {
+    let data = table();
+    let offset = HEADER_SIZE + index * ENTRY_SIZE;
+    let name_at = (u32_at(data, offset + 12) as usize).min(KSYMS_SIZE - 1);
+    let len = (data[name_at] as usize).min(KSYMS_SIZE - name_at - 1);
+    Symbol {
+        addr: u64_at(data, offset),
+        size: u32_at(data, offset + 8) as u64,
+        name: core::str::from_utf8(&data[name_at + 1..name_at + 1 + len]).unwrap_or("?"),
+    }
+}
+
+// addr를 포함하는 함수
+pub fn lookup(addr: u64) -> Option<Symbol> {
+    let (mut low, mut high) = (0, count());
+    while low < high {
+        let mid = (low + high) / 2;
+        if entry(mid).addr <= addr {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    let symbol = entry(low.checked_sub(1)?);
+    if symbol.size != 0 && addr - symbol.addr >= symbol.size {
+        return None;
+    }
+    Some(symbol)
+}
@@ -1,4 +1,154 @@
+// kernel/src/lib.rs - 커널 공통 모듈과 테스트 프레임워크
 #![no_std]
+#![cfg_attr(test, no_main)]
+#![feature(alloc_error_handler)]
+#![feature(abi_x86_interrupt)]
+#![feature(custom_test_frameworks)]
+#![test_runner(crate::test_runner)]
+#![reexport_test_harness_main = "test_main"]
 
-// 여기에 향후 커널의 공통 모듈이나 함수들이 위치하게 됩니다.
-// 현재는 비어있어도 빌드 오류는 해결됩니다.
\ No newline at end of file
+extern crate alloc;
+
+use core::fmt;
+use core::panic::PanicInfo;
+use x86_64::instructions::hlt;
+
+pub mod serial;
+pub mod memory;
+pub mod interrupts;
+pub mod keyboard;
+pub mod ps2;
+pub mod mouse;
+pub mod input;
+pub mod power;
+pub mod klog;
+pub mod gdb;
+pub mod crashdump;
+pub mod ksyms;
+pub mod profiler;
+pub mod bootlog;
+pub mod watchpoint;
+pub mod console;
+pub mod fbcon;
+
+// 하드웨어와 무관한 로직 (호스트에서 테스트)
+pub use kernel_core::{color, format, hangul, keymap, shell};
+
+pub static mut TICK_COUNTER: u64 = 0;
+
+#[no_mangle]
+pub fn increment_tick() {
+    unsafe { TICK_COUNTER = TICK_COUNTER.wrapping_add(1); }
+}
+
+// 고정 크기 줄 버퍼 (힙 없이 포맷 출력, 넘치면 잘라냄)
+pub struct LineBuf {
+    buf: [u8; 256],
+    len: usize,
+}
+
+impl Default for LineBuf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LineBuf {
+    pub const fn new() -> Self {
+        LineBuf { buf: [0; 256], len: 0 }
+    }
+    
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or("")
+    }
+}
+
+impl fmt::Write for LineBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for ch in s.chars() {
+            let n = ch.len_utf8();
+            if self.len + n > self.buf.len() {
+                break;
+            }
+            ch.encode_utf8(&mut self.buf[self.len..]);
+            self.len += n;
+        }
+        Ok(())
+    }
+}
+
+// 테스트용 초기화 (본 부팅 순서는 main.rs의 _start)
+pub fn init() {
+    serial::init();
+    interrupts::init_gdt();
+    interrupts::init_idt();
+    memory::init_heap();
+    interrupts::init_pit();
+    interrupts::init_pics();
+    interrupts::enable_interrupts();
+}
+
+pub fn hlt_loop() -> ! {
+    loop { hlt(); }
+}
+
+// QEMU isa-debug-exit 장치 (iobase=0xf4) -> 종료 코드 (값 << 1) | 1
+const QEMU_EXIT_PORT: u16 = 0xf4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum QemuExitCode {
+    Success = 0x10, // 33
+    Failed = 0x11,  // 35
+}
+
+pub fn exit_qemu(exit_code: QemuExitCode) -> ! {
+    use x86_64::instructions::port::Port;
+    unsafe {
+        let mut port = Port::<u32>::new(QEMU_EXIT_PORT);
+        port.write(exit_code as u32);
+    }
+    // QEMU가 아니면 여기까지 옴
+    hlt_loop();
+}
+
+// 테스트 이름을 출력하고 실행
+pub trait Testable {
+    fn run(&self);
+}
+
+impl<T: Fn()> Testable for T {
+    fn run(&self) {
+        serial_print!("{}...\t", core::any::type_name::<T>());
+        self();
+        serial_println!("[ok]");
+    }
+}
+
+pub fn test_runner(tests: &[&dyn Testable]) {
+    serial_println!("Running {} tests", tests.len());
+    for test in tests {
+        test.run();
+    }
+    exit_qemu(QemuExitCode::Success);
+}
+
+pub fn test_panic_handler(info: &PanicInfo) -> ! {
+    serial_println!("[failed]");
+    serial_println!("Error: {}", info);
+    exit_qemu(QemuExitCode::Failed);
+}
+
+#[cfg(test)]
+#[no_mangle]
+pub extern "C" fn _start() -> ! {
+    init();
+    test_main();
+    hlt_loop();
+}
+
+#[cfg(test)]
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    test_panic_handler(info)
+}
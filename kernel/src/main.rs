@@ -1,385 +1,853 @@
 // kernel/src/main.rs - 인터럽트 기반 키보드 입력
 #![no_std]
 #![no_main]
-#![feature(alloc_error_handler)]
-#![feature(abi_x86_interrupt)] 
+#![feature(custom_test_frameworks)]
+#![test_runner(myos_kernel::test_runner)]
+#![reexport_test_harness_main = "test_main"]
 
 extern crate alloc;
 
 use core::panic::PanicInfo;
+use log::{error, info};
 use x86_64::instructions::hlt;
 
-mod shell;
-mod memory;
-mod interrupts;
-
+use core::fmt::Write;
+use myos_kernel::print;
+use myos_kernel::{bootlog, console, gdb, input, interrupts, keyboard, keymap, hangul, klog, ksyms, memory, mouse, power, profiler, ps2, serial, shell, watchpoint};
+use myos_kernel::{LineBuf, TICK_COUNTER};
+use myos_kernel::format::format_uptime;
 use shell::Shell;
-
-static mut TICK_COUNTER: u64 = 0;
-static mut BG_COLOR: u8 = 0x0;
-
-#[no_mangle]
-pub fn increment_tick() {
-    unsafe { TICK_COUNTER = TICK_COUNTER.wrapping_add(1); }
-}
-
-fn init_pit() {
-    use x86_64::instructions::port::Port;
-    unsafe {
-        let divisor: u16 = 11932; // 100Hz
-        let mut command_port = Port::<u8>::new(0x43);
-        let mut data_port = Port::<u8>::new(0x40);
-        
-        command_port.write(0x36);
-        data_port.write((divisor & 0xFF) as u8);
-        data_port.write((divisor >> 8) as u8);
-    }
-}
+use keyboard::{KeyCode, KeyEvent};
+use hangul::HangulIme;
+use input::{InputEventKind, Subscriber};
+use kernel_core::font::{embolden, halve_height};
+use vga_driver::font::GLYPHS;
+use vga_driver::graphics::GraphicsMode;
+use vga_driver::mode::TextMode;
+use vga_driver::palette;
 
 #[no_mangle]
 pub extern "C" fn _start() -> ! {
-    clear_screen();
-    vga_write(0, 0, "=== AerogelOS v0.1.0 ===", 0x0E);
-    vga_write(0, 1, "[1/4] Initializing GDT...", 0x07);
-    
-    interrupts::init_gdt();
-    vga_write(0, 1, "[2/4] Initializing IDT...    ", 0x07);
+    // cargo test 바이너리: 부팅 전에 테스트를 돌리고 QEMU 종료 (커널 테스트는 lib.rs와 tests/*.rs)
+    #[cfg(test)]
+    test_main();
     
-    interrupts::init_idt();
-    vga_write(0, 1, "[3/4] Initializing heap...   ", 0x07);
+    bootlog::start();
+    serial::init();
+    // 화면에 보이는 터미널의 출력은 콘솔 계층이 시리얼에도 복사
+    console::set_serial_mirror(true);
+    console::with_vt(0, |c| c.clear());
+    status_line(0, 0, "=== AerogelOS v0.1.0 ===", 0x0E);
     
-    memory::init_heap();
-    vga_write(0, 1, "[4/4] Starting interrupts... ", 0x07);
+    // 부팅 중에는 로그를 VGA에도 출력
+    klog::init(klog::Sinks { vga: true, serial: true, debugcon: true });
     
-    init_pit();
-    interrupts::init_pics();
-    interrupts::enable_interrupts();
-    
-    vga_write(0, 1, "[DONE] System ready! (Interrupt Mode)", 0x0A);
-    vga_write(0, 2, "Welcome to AerogelOS!", 0x0F);
-    vga_write(0, 3, "Type 'help' for available commands", 0x07);
+    // 각 단계는 TSC로 시간을 재고 결과를 남김 (bootlog 명령)
+    bootlog::stage("GDT", interrupts::init_gdt, |_| true);
+    bootlog::stage("IDT", interrupts::init_idt, |_| true);
+    bootlog::stage("Heap", memory::init_heap, |_| memory::try_heap_stats().is_some());
+    let ps2_status = bootlog::stage("PS/2 controller", ps2::init, |s| s.controller_ok && s.keyboard_ok);
+    // 마우스가 없는 것은 실패가 아님
+    bootlog::stage("Mouse", || mouse::init(&ps2_status), |&ok| ok || !ps2_status.mouse_ok);
+    bootlog::stage("PIT", interrupts::init_pit, |_| true);
+    bootlog::stage("PIC", interrupts::init_pics, |_| true);
+    bootlog::stage("Interrupts", interrupts::enable_interrupts, |_| interrupts::are_interrupts_enabled());
+    bootlog::stage("Keyboard LEDs", keyboard::init, |_| true);
     
     // 인터럽트 상태 확인
-    let enabled = interrupts::are_interrupts_enabled();
-    vga_write(0, 4, if enabled { "INT: ON " } else { "INT: OFF" }, if enabled { 0x0A } else { 0x0C });
+    if interrupts::are_interrupts_enabled() {
+        info!("System ready! (Interrupt Mode)");
+    } else {
+        error!("Interrupts are disabled");
+    }
     
-    vga_write(0, 5, "> ", 0x0F);
+    // 부팅이 끝나면 VGA 화면은 셸이 사용 (로그는 dmesg로 확인)
+    klog::set_sinks(klog::Sinks { vga: false, serial: true, debugcon: true });
+    put_line(0, "Welcome to AerogelOS!", 0x0F);
+    put_line(0, "Type 'help' for available commands", 0x07);
+    draw_input_line(0, "", Some(0));
     
-    let mut shell = Shell::new();
-    shell.set_boot_time(unsafe { TICK_COUNTER });
-    let mut current_row: usize = 5;
+    // 가상 터미널마다, 그리고 시리얼 세션이 각자의 셸과 입력 줄을 가짐
+    let boot_time = unsafe { TICK_COUNTER };
+    let mut terminals: [Session; console::VT_COUNT] =
+        core::array::from_fn(|vt| Session::new(Terminal::Vt(vt), boot_time));
+    for session in &mut terminals[1..] {
+        session.clear();
+        session.prompt();
+    }
+    let mut remote = Session::new(Terminal::Serial, boot_time);
+    remote.prompt();
+    
+    let input = input::subscribe().expect("no free input subscriber slot");
     
     loop {
-        // 인터럽트 버퍼에서 스캔코드 읽기
-        if let Some(scancode) = interrupts::read_scancode() {
-            if let Some(ch) = scancode_to_char(scancode) {
-                if ch == '\n' {
-                    let result = shell.execute(unsafe { TICK_COUNTER });
-                    
-                    current_row += 1;
-                    if current_row >= 24 {
-                        scroll_up();
-                        current_row = 23;
-                    }
-                    
-                    match result {
-                        shell::ShellResult::Clear => {
-                            clear_screen();
-                            vga_write(0, 0, "=== AerogelOS v0.1.0 ===", 0x0E);
-                            vga_write(0, 1, "Type 'help' for commands", 0x07);
-                            current_row = 3;
-                        },
-                        shell::ShellResult::Shutdown => {
-                            clear_screen();
-                            vga_write(30, 12, "Shutting down...", 0x0C);
-                            let mut port = x86_64::instructions::port::Port::<u16>::new(0x604);
-                            unsafe { port.write(0x2000); }
-                            loop { hlt(); }
-                        },
-                        shell::ShellResult::Reboot => {
-                            clear_screen();
-                            vga_write(32, 12, "Rebooting...", 0x0E);
-                            unsafe {
-                                let mut port = x86_64::instructions::port::Port::<u8>::new(0x64);
-                                port.write(0xFE);
-                            }
-                            loop { hlt(); }
-                        },
-                        shell::ShellResult::CpuInfo => {
-                            vga_write(0, current_row, "CPU: x86_64 compatible", 0x0B);
-                            current_row += 1;
-                            vga_write(0, current_row, "Vendor: (Use CPUID for details)", 0x07);
-                            current_row += 1;
-                            if current_row >= 24 { scroll_up(); current_row = 23; }
-                        },
-                        shell::ShellResult::MemInfo => {
-                            use alloc::vec::Vec;
-                            use alloc::string::String;
-                            
-                            vga_write(0, current_row, "Testing memory allocator...", 0x0E);
-                            current_row += 1;
-                            if current_row >= 24 { scroll_up(); current_row = 23; }
-                            
-                            let mut test_vec = Vec::new();
-                            for i in 0..10 {
-                                test_vec.push(i * 10);
-                            }
-                            let test_string = String::from("Heap OK!");
-                            
-                            vga_write(0, current_row, "Heap: 200KB allocated, Status: OK", 0x0A);
-                            current_row += 1;
-                            if current_row >= 24 { scroll_up(); current_row = 23; }
-                            
-                            drop(test_vec);
-                            drop(test_string);
-                        },
-                        shell::ShellResult::SysInfo => {
-                            vga_write(0, current_row, "=== System Information ===", 0x0E);
-                            current_row += 1;
-                            if current_row >= 24 { scroll_up(); current_row = 23; }
-                            
-                            vga_write(0, current_row, "OS: AerogelOS v0.1.0", 0x0B);
-                            current_row += 1;
-                            if current_row >= 24 { scroll_up(); current_row = 23; }
-                            
-                            vga_write(0, current_row, "Architecture: x86_64", 0x0B);
-                            current_row += 1;
-                            if current_row >= 24 { scroll_up(); current_row = 23; }
-                            
-                            vga_write(0, current_row, "Memory: 200KB heap", 0x0B);
-                            current_row += 1;
-                            if current_row >= 24 { scroll_up(); current_row = 23; }
-                            
-                            vga_write(0, current_row, "Timer: 100Hz PIT", 0x0B);
-                            current_row += 1;
-                            if current_row >= 24 { scroll_up(); current_row = 23; }
-                        },
-                        shell::ShellResult::DateTime => {
-                            vga_write(0, current_row, "Date: 2025-01-XX (RTC not impl)", 0x0B);
-                            current_row += 1;
-                            if current_row >= 24 { scroll_up(); current_row = 23; }
-                            
-                            vga_write(0, current_row, "Time: HH:MM:SS (RTC not impl)", 0x0B);
-                            current_row += 1;
-                            if current_row >= 24 { scroll_up(); current_row = 23; }
-                        },
-                        shell::ShellResult::Uptime(ticks) => {
-                            let seconds = ticks / 100;
-                            let minutes = seconds / 60;
-                            let hours = minutes / 60;
-                            
-                            let mut buf = [0u8; 64];
-                            let text = format_uptime(hours, minutes % 60, seconds % 60, &mut buf);
-                            vga_write(0, current_row, text, 0x0B);
-                            current_row += 1;
-                            if current_row >= 24 { scroll_up(); current_row = 23; }
-                        },
-                        shell::ShellResult::BgColor(color) => {
-                            unsafe { BG_COLOR = color; }
-                            change_background(color);
-                            vga_write(0, current_row, "Background color changed!", 0x0A);
-                            current_row += 1;
-                            if current_row >= 24 { scroll_up(); current_row = 23; }
-                        },
-                        shell::ShellResult::Output(text) => {
-                            vga_write(0, current_row, text, 0x0A);
-                            current_row += 1;
-                            if current_row >= 24 { scroll_up(); current_row = 23; }
-                        },
-                        shell::ShellResult::MultiOutput(lines, count) => {
-                            for i in 0..count {
-                                vga_write(0, current_row, lines[i], 0x0B);
-                                current_row += 1;
-                                if current_row >= 24 { scroll_up(); current_row = 23; }
-                            }
-                        },
-                        shell::ShellResult::Print(buf, len) => {
-                            let text = core::str::from_utf8(&buf[..len]).unwrap_or("");
-                            vga_write(0, current_row, text, 0x0F);
-                            current_row += 1;
-                            if current_row >= 24 { scroll_up(); current_row = 23; }
-                        },
-                        shell::ShellResult::Empty => {},
-                    }
-                    
-                    vga_write(0, current_row, "> ", 0x0F);
-                    
-                } else if ch == '\x08' {
-                    shell.backspace();
-                    clear_line(current_row);
-                    vga_write(0, current_row, "> ", 0x0F);
-                    vga_write(2, current_row, shell.get_buffer(), 0x0F);
-                } else {
-                    shell.add_char(ch);
-                    clear_line(current_row);
-                    vga_write(0, current_row, "> ", 0x0F);
-                    vga_write(2, current_row, shell.get_buffer(), 0x0F);
-                }
-            }
+        // 입력 큐에서 이벤트 읽기 (없으면 hlt로 대기)
+        match input.wait().kind {
+            InputEventKind::Key(event) if event.is_pressed() => match vt_hotkey(&event) {
+                Some(vt) => console::switch_vt(vt),
+                None => handle_key(&mut terminals[console::active_vt()], &event, &input),
+            },
+            InputEventKind::Serial(byte) => handle_serial_byte(&mut remote, byte, &input),
+            _ => {},
         }
-        
-        hlt(); // 인터럽트 대기
     }
 }
 
-fn format_uptime(hours: u64, minutes: u64, seconds: u64, buf: &mut [u8]) -> &str {
-    let mut pos = 0;
-    let prefix = b"Uptime: ";
-    for &b in prefix {
-        buf[pos] = b;
-        pos += 1;
+// 셸 출력 대상
+enum Terminal {
+    Vt(usize), // 가상 터미널 (보이는 동안은 시리얼에도 미러링)
+    Serial,    // 시리얼 콘솔 전용
+}
+
+// 셸 세션: 입력 줄 버퍼와 출력 대상을 각자 가짐
+struct Session {
+    shell: Shell,
+    ime: HangulIme,
+    term: Terminal,
+    last_cr: bool, // 시리얼: CR LF를 한 번의 Enter로 처리
+}
+
+impl Session {
+    fn new(term: Terminal, boot_time: u64) -> Self {
+        let mut shell = Shell::new();
+        shell.set_boot_time(boot_time);
+        Session { shell, ime: HangulIme::new(), term, last_cr: false }
     }
     
-    if hours > 0 {
-        pos += write_num(&mut buf[pos..], hours);
-        buf[pos] = b'h';
-        pos += 1;
-        buf[pos] = b' ';
-        pos += 1;
+    fn print(&mut self, text: &str) {
+        match self.term {
+            Terminal::Vt(vt) => print_vt(vt, text),
+            Terminal::Serial => self.with_console(|c| c.write_str(text)),
+        }
     }
     
-    if minutes > 0 || hours > 0 {
-        pos += write_num(&mut buf[pos..], minutes);
-        buf[pos] = b'm';
-        pos += 1;
-        buf[pos] = b' ';
-        pos += 1;
+    fn put_line(&mut self, text: &str, color: u8) {
+        match self.term {
+            Terminal::Vt(vt) => put_line(vt, text, color),
+            Terminal::Serial => {
+                self.print(text);
+                self.print("\n");
+            },
+        }
     }
     
-    pos += write_num(&mut buf[pos..], seconds);
-    buf[pos] = b's';
-    pos += 1;
+    fn prompt(&mut self) {
+        match self.term {
+            Terminal::Vt(vt) => draw_input_line(vt, "", Some(0)),
+            Terminal::Serial => self.print("> "),
+        }
+    }
+    
+    fn clear(&mut self) {
+        match self.term {
+            Terminal::Vt(vt) => {
+                console::with_vt(vt, |c| c.clear());
+                let mut title = LineBuf::new();
+                let _ = write!(title, "=== AerogelOS v0.1.0 === tty{}", vt + 1);
+                status_line(vt, 0, title.as_str(), 0x0E);
+                status_line(vt, 1, "Type 'help' for commands, Alt+F1..F6 to switch terminals", 0x07);
+                console::with_vt(vt, |c| c.set_cursor(0, 3));
+            },
+            Terminal::Serial => self.with_console(|c| c.clear()),
+        }
+    }
     
-    core::str::from_utf8(&buf[..pos]).unwrap_or("Uptime: N/A")
+    // 세션의 출력 콘솔
+    fn with_console<R>(&self, f: impl FnOnce(&mut dyn console::Console) -> R) -> R {
+        match self.term {
+            Terminal::Vt(vt) => console::with_vt(vt, f),
+            Terminal::Serial => x86_64::instructions::interrupts::without_interrupts(|| {
+                f(&mut *serial::SERIAL_CONSOLE.lock())
+            }),
+        }
+    }
 }
 
-fn write_num(buf: &mut [u8], mut num: u64) -> usize {
-    if num == 0 {
-        buf[0] = b'0';
-        return 1;
+// Alt+F1..F6 -> 가상 터미널 번호
+fn vt_hotkey(event: &KeyEvent) -> Option<usize> {
+    if !event.modifiers.alt() {
+        return None;
     }
+    let vt = match event.code {
+        KeyCode::F1 => 0,
+        KeyCode::F2 => 1,
+        KeyCode::F3 => 2,
+        KeyCode::F4 => 3,
+        KeyCode::F5 => 4,
+        KeyCode::F6 => 5,
+        _ => return None,
+    };
+    Some(vt)
+}
+
+// 가상 터미널 세션의 키 입력 처리
+fn handle_key(session: &mut Session, event: &KeyEvent, input: &Subscriber) {
+    let Terminal::Vt(vt) = session.term else { return };
     
-    let mut digits = [0u8; 20];
-    let mut count = 0;
-    while num > 0 {
-        digits[count] = (num % 10) as u8 + b'0';
-        num /= 10;
-        count += 1;
+    // Shift+PgUp/PgDn: 스크롤백 보기 (반 화면씩)
+    if event.modifiers.shift() && matches!(event.code, KeyCode::PageUp | KeyCode::PageDown) {
+        console::with_vt(vt, |c| {
+            let half = (c.size().1 / 2) as isize;
+            c.scroll_view(if event.code == KeyCode::PageUp { half } else { -half });
+        });
+        return;
     }
+    let shell = &mut session.shell;
+    let ime = &mut session.ime;
     
-    for i in 0..count {
-        buf[i] = digits[count - 1 - i];
+    if event.ch == Some('\n') {
+        if let Some(ch) = ime.flush() {
+            shell.add_char(ch);
+        }
+        commit_input_line(vt, shell.get_buffer());
+        print_vt(vt, "\n");
+        run_command(session, input);
+    } else if event.ch == Some(keyboard::CTRL_C) {
+        // 실행 중인 명령이 이미 처리한 Ctrl+C는 건너뜀
+        if input::take_interrupt() {
+            *ime = HangulIme::new();
+            commit_input_line(vt, shell.get_buffer());
+            put_line(vt, "^C", 0x0C);
+            shell.clear();
+            draw_input_line(vt, "", Some(0));
+        }
+    } else if event.ch == Some(keyboard::CTRL_D) && shell.get_buffer().is_empty() {
+        // 빈 줄에서 EOF: 셸은 종료하지 않음
+        commit_input_line(vt, "");
+        print_vt(vt, "\n");
+        put_line(vt, "Use 'shutdown' or 'reboot' to leave.", 0x07);
+        draw_input_line(vt, "", Some(0));
+    } else if event.ch == Some(keyboard::CTRL_D) {
+        // 내용이 있으면 커서 위치 문자 삭제
+        shell.delete();
+        redraw_input(vt, shell, ime);
+    } else if edit_line(shell, ime, event) {
+        redraw_input(vt, shell, ime);
     }
-    count
 }
 
-fn change_background(color: u8) {
-    let vga = 0xb8000 as *mut u8;
-    for i in 0..(80 * 25) {
-        unsafe {
-            let current_fg = *vga.offset((i * 2 + 1) as isize) & 0x0F;
-            *vga.offset((i * 2 + 1) as isize) = (color << 4) | current_fg;
-        }
+// 시리얼 세션의 바이트 입력 처리 (터미널 에코는 여기서 직접)
+fn handle_serial_byte(session: &mut Session, byte: u8, input: &Subscriber) {
+    let after_cr = core::mem::replace(&mut session.last_cr, byte == b'\r');
+    
+    match byte {
+        // CR, LF, CR LF 모두 Enter 한 번
+        b'\n' if after_cr => {},
+        b'\r' | b'\n' => {
+            session.print("\n");
+            run_command(session, input);
+        },
+        // 터미널마다 BS(0x08) 또는 DEL(0x7F)을 보냄
+        0x08 | 0x7F => {
+            if !session.shell.get_buffer().is_empty() {
+                session.shell.backspace();
+                session.print("\x08 \x08");
+            }
+        },
+        0x03 => {
+            if input::take_interrupt() {
+                session.print("^C\n");
+                session.shell.clear();
+                session.prompt();
+            }
+        },
+        0x04 if session.shell.get_buffer().is_empty() => {
+            session.print("\n");
+            session.put_line("Use 'shutdown' or 'reboot' to leave.", 0x07);
+            session.prompt();
+        },
+        0x20..=0x7E => {
+            let len = session.shell.get_buffer().len();
+            session.shell.add_char(byte as char);
+            if session.shell.get_buffer().len() != len {
+                let mut buf = [0; 4];
+                session.print((byte as char).encode_utf8(&mut buf));
+            }
+        },
+        _ => {},
     }
 }
 
-fn vga_write(x: usize, y: usize, s: &str, color: u8) {
-    let vga = 0xb8000 as *mut u8;
-    let offset = (y * 80 + x) * 2;
-    let bg = unsafe { BG_COLOR };
-    let full_color = (bg << 4) | color;
+// 입력 줄 실행 후 결과를 세션 출력 대상에 표시하고 새 프롬프트
+fn run_command(session: &mut Session, input: &Subscriber) {
+    input::take_interrupt(); // 이전에 눌린 Ctrl+C는 무시
+    let result = session.shell.execute(unsafe { TICK_COUNTER });
     
-    for (i, byte) in s.bytes().enumerate() {
-        if x + i >= 80 { break; }
-        unsafe {
-            *vga.offset((offset + i * 2) as isize) = byte;
-            *vga.offset((offset + i * 2 + 1) as isize) = full_color;
-        }
+    match result {
+        shell::ShellResult::Clear => session.clear(),
+        shell::ShellResult::Shutdown => power::shutdown(),
+        shell::ShellResult::Reboot => power::reboot(),
+        shell::ShellResult::CpuInfo => {
+            session.put_line("CPU: x86_64 compatible", 0x0B);
+            session.put_line("Vendor: (Use CPUID for details)", 0x07);
+        },
+        shell::ShellResult::MemInfo => {
+            use alloc::vec::Vec;
+            use alloc::string::String;
+            
+            session.put_line("Testing memory allocator...", 0x0E);
+            
+            let mut test_vec = Vec::new();
+            for i in 0..10 {
+                test_vec.push(i * 10);
+            }
+            let test_string = String::from("Heap OK!");
+            
+            session.put_line("Heap: 200KB allocated, Status: OK", 0x0A);
+            
+            drop(test_vec);
+            drop(test_string);
+        },
+        shell::ShellResult::SysInfo => {
+            session.put_line("=== System Information ===", 0x0E);
+            
+            session.put_line("OS: AerogelOS v0.1.0", 0x0B);
+            
+            session.put_line("Architecture: x86_64", 0x0B);
+            
+            session.put_line("Memory: 200KB heap", 0x0B);
+            
+            session.put_line("Timer: 100Hz PIT", 0x0B);
+            
+            let stats = input::stats();
+            let mut line = LineBuf::new();
+            let _ = write!(line, "Input: {} events, {} overflows ({} lost here), {} subscribers",
+                stats.events, stats.overflows, input.dropped(), stats.subscribers);
+            session.put_line(line.as_str(), 0x0B);
+            
+            let mouse = if !mouse::is_present() {
+                "Mouse: none"
+            } else if mouse::has_wheel() {
+                "Mouse: PS/2 (wheel)"
+            } else {
+                "Mouse: PS/2"
+            };
+            session.put_line(mouse, 0x0B);
+        },
+        shell::ShellResult::DateTime => {
+            session.put_line("Date: 2025-01-XX (RTC not impl)", 0x0B);
+            
+            session.put_line("Time: HH:MM:SS (RTC not impl)", 0x0B);
+        },
+        shell::ShellResult::Uptime(ticks) => {
+            let seconds = ticks / 100;
+            let minutes = seconds / 60;
+            let hours = minutes / 60;
+            
+            let mut buf = [0u8; 64];
+            let text = format_uptime(hours, minutes % 60, seconds % 60, &mut buf);
+            session.put_line(text, 0x0B);
+        },
+        shell::ShellResult::BgColor(color) => {
+            session.with_console(|c| c.set_background(color));
+            session.put_line("Background color changed!", 0x0A);
+        },
+        shell::ShellResult::Output(text) => {
+            session.put_line(text, 0x0A);
+        },
+        shell::ShellResult::MultiOutput(lines) => {
+            for line in lines {
+                session.put_line(line, 0x0B);
+            }
+        },
+        shell::ShellResult::Print(buf, len) => {
+            let text = core::str::from_utf8(&buf[..len]).unwrap_or("");
+            session.put_line(text, 0x0F);
+        },
+        shell::ShellResult::KbdLayout(Some(layout)) => {
+            keyboard::set_layout(layout);
+            let mut line = LineBuf::new();
+            let _ = write!(line, "Keyboard layout: {} ({})", layout.name, layout.description);
+            session.put_line(line.as_str(), 0x0A);
+        },
+        shell::ShellResult::KbdLayout(None) => {
+            let current = keyboard::layout();
+            let mut line = LineBuf::new();
+            let _ = write!(line, "Current layout: {}", current.name);
+            if current.has_hangul() {
+                let _ = write!(line, " [{}]", if keyboard::hangul_mode() { "Han" } else { "Eng" });
+            }
+            session.put_line(line.as_str(), 0x0B);
+            
+            for layout in keymap::LAYOUTS {
+                let mut line = LineBuf::new();
+                let _ = write!(line, "  {:<8} - {}", layout.name, layout.description);
+                session.put_line(line.as_str(), 0x07);
+            }
+        },
+        shell::ShellResult::MouseTest => {
+            if !mouse::is_present() {
+                session.put_line("No PS/2 mouse detected", 0x0C);
+            } else if let Terminal::Serial = session.term {
+                session.put_line("mousetest needs the VGA console", 0x0C);
+            } else {
+                run_mouse_test(input);
+                session.clear();
+            }
+        },
+        shell::ShellResult::Dmesg(level) => show_log(session, klog::first_seq(), level),
+        shell::ShellResult::Debugger => {
+            session.put_line("Waiting for GDB on COM2 (target remote ...)", 0x0E);
+            info!("entering GDB stub on COM2 ({:#x})", gdb::COM2);
+            gdb::break_in();
+            session.put_line("Resumed from debugger", 0x0A);
+        },
+        shell::ShellResult::Profile(shell::ProfileCommand::Start) => {
+            profiler::start();
+            session.put_line("Profiler started (100 samples/s)", 0x0A);
+        },
+        shell::ShellResult::Profile(shell::ProfileCommand::Stop) => {
+            profiler::stop();
+            session.put_line("Profiler stopped", 0x0A);
+        },
+        shell::ShellResult::Profile(shell::ProfileCommand::Report) => show_profile(session),
+        shell::ShellResult::BootLog => show_bootlog(session),
+        shell::ShellResult::Watch(command) => run_watch(session, command),
+        shell::ShellResult::Int3 => {
+            // 핸들러가 남긴 로그를 그대로 보여줌
+            let seq = klog::next_seq();
+            x86_64::instructions::interrupts::int3();
+            show_log(session, seq, log::LevelFilter::Trace);
+            session.put_line("Resumed after int3", 0x0A);
+        },
+        shell::ShellResult::Mode(None) => {
+            let current = console::text_mode();
+            for mode in TextMode::ALL {
+                let mut line = LineBuf::new();
+                let _ = write!(line, "  {}x{}{}", mode.width(), mode.height(),
+                    if mode == current { " (current)" } else { "" });
+                session.put_line(line.as_str(), 0x0B);
+            }
+        },
+        shell::ShellResult::Mode(Some((cols, rows))) => match TextMode::from_size(cols, rows) {
+            Some(mode) => {
+                console::set_text_mode(mode);
+                let mut line = LineBuf::new();
+                let _ = write!(line, "Text mode: {}x{}", cols, rows);
+                session.put_line(line.as_str(), 0x0A);
+            },
+            None => session.put_line("Unsupported mode! Use 80x25, 80x50 or 90x60", 0x0C),
+        },
+        shell::ShellResult::Font(style) => {
+            match style {
+                shell::FontStyle::Bios => console::reset_font(),
+                shell::FontStyle::Bold => load_bold_font(),
+            }
+            session.put_line("Font changed", 0x0A);
+        },
+        shell::ShellResult::GfxTest(width, height) => match GraphicsMode::from_size(width, height) {
+            None => session.put_line("Unsupported mode! Use 320x200 or 640x480", 0x0C),
+            Some(_) if matches!(session.term, Terminal::Serial) => {
+                session.put_line("gfxtest needs the VGA console", 0x0C);
+            },
+            Some(mode) => {
+                run_graphics_test(input, mode);
+                session.put_line("Back in text mode", 0x0A);
+            },
+        },
+        shell::ShellResult::Sleep(ticks) => {
+            let end = interrupts::get_timer_ticks().saturating_add(ticks);
+            while interrupts::get_timer_ticks() < end {
+                if input::take_interrupt() {
+                    session.put_line("^C", 0x0C);
+                    break;
+                }
+                hlt();
+            }
+        },
+        shell::ShellResult::Empty => {},
     }
+    
+    session.prompt();
 }
 
-fn clear_screen() {
-    let vga = 0xb8000 as *mut u8;
-    let bg = unsafe { BG_COLOR };
-    for i in 0..(80 * 25) {
-        unsafe {
-            *vga.offset((i * 2) as isize) = b' ';
-            *vga.offset((i * 2) as isize + 1) = (bg << 4) | 0x0F;
-        }
-    }
+// BIOS 글꼴을 굵게 만들어 8x16, 8x8 글꼴로 올림
+fn load_bold_font() {
+    use alloc::vec;
+    let mut bios = vec![0; GLYPHS * 16];
+    console::bios_font(&mut bios);
+    let mut bold16 = vec![0; GLYPHS * 16];
+    embolden(&bios, &mut bold16);
+    let mut bold8 = vec![0; GLYPHS * 8];
+    halve_height(&bold16, 16, &mut bold8);
+    console::load_font(&bold16, 16);
+    console::load_font(&bold8, 8);
 }
 
-fn clear_line(row: usize) {
-    let vga = 0xb8000 as *mut u8;
-    let bg = unsafe { BG_COLOR };
-    for i in 0..80 {
-        unsafe {
-            *vga.offset(((80 * row + i) * 2) as isize) = b' ';
-            *vga.offset(((80 * row + i) * 2 + 1) as isize) = (bg << 4) | 0x0F;
+// mousetest: 텍스트 모드 마우스 커서 (아무 키나 누르면 종료)
+fn run_mouse_test(input: &Subscriber) {
+    const CELL_W: i32 = 8;  // 한 칸당 마우스 이동량
+    const CELL_H: i32 = 16;
+    
+    console::with(|c| {
+        c.clear();
+        c.show_cursor(false); // 마우스 커서와 헷갈리지 않도록
+        c.set_color(0x0E);
+        c.write_str("=== Mouse Test === (press any key to exit)");
+    });
+    
+    let (width, height) = console::with(|c| c.size());
+    let (width, height) = (width as i32, height as i32);
+    let mut x: i32 = width / 2 * CELL_W;
+    let mut y: i32 = height / 2 * CELL_H;
+    let mut wheel: i32 = 0;
+    let mut buttons = mouse::MouseButtons { left: false, right: false, middle: false };
+    let mut saved: Option<(usize, usize, u8)> = None; // 커서 칸 열, 행, 원래 속성
+    let mut dirty = true;
+    
+    loop {
+        if dirty {
+            dirty = false;
+            let col = (x / CELL_W) as usize;
+            let row = (y / CELL_H) as usize;
+            
+            let mut line = LineBuf::new();
+            let _ = write!(line, "X: {:2}  Y: {:2}  Wheel: {:4}  [{}] [{}] [{}]", col, row, wheel,
+                if buttons.left { 'L' } else { ' ' },
+                if buttons.middle { 'M' } else { ' ' },
+                if buttons.right { 'R' } else { ' ' });
+            console::with(|c| {
+                c.clear_row(1);
+                c.set_cursor(0, 1);
+                c.set_color(0x0B);
+                c.write_str(line.as_str());
+            });
+            
+            // 이전 커서 지우고 새 위치에 그리기 (배경/전경 반전, 버튼을 누르면 노란색)
+            x86_64::instructions::interrupts::without_interrupts(|| {
+                let mut vga = console::active_terminal().lock();
+                if let Some((col, row, attr)) = saved.take() {
+                    let (byte, _) = vga.cell(col, row);
+                    vga.write_cell(col, row, byte, attr);
+                }
+                let (byte, attr) = vga.cell(col, row);
+                let pressed = buttons.left || buttons.right || buttons.middle;
+                vga.write_cell(col, row, byte, if pressed { 0x4E } else { attr.rotate_left(4) });
+                saved = Some((col, row, attr));
+            });
+        }
+        
+        match input.wait().kind {
+            InputEventKind::Key(event) if event.is_pressed() && event.ch.is_some() => {
+                input::take_interrupt(); // Ctrl+C로 나간 경우 프롬프트에서 다시 처리하지 않음
+                break;
+            },
+            InputEventKind::Mouse(event) => {
+                x = (x + event.dx as i32).clamp(0, width * CELL_W - 1);
+                y = (y - event.dy as i32).clamp(2 * CELL_H, height * CELL_H - 1);
+                wheel += event.wheel as i32;
+                buttons = event.buttons;
+                dirty = true;
+            },
+            _ => {},
         }
     }
 }
 
-fn scroll_up() {
-    let vga = 0xb8000 as *mut u8;
-    unsafe {
-        for row in 1..25 {
-            for col in 0..80 {
-                let src_offset = ((row * 80 + col) * 2) as isize;
-                let dst_offset = (((row - 1) * 80 + col) * 2) as isize;
-                *vga.offset(dst_offset) = *vga.offset(src_offset);
-                *vga.offset(dst_offset + 1) = *vga.offset(src_offset + 1);
+// gfxtest: 팔레트, 선, 사각형, 블릿을 그리고 아무 키나 누르면 텍스트 모드로 복귀
+fn run_graphics_test(input: &Subscriber, mode: GraphicsMode) {
+    const SPRITE: usize = 16;
+    
+    let canvas = console::enter_graphics(mode);
+    let (width, height) = canvas.size();
+    let (w, h) = (width as i32, height as i32);
+    let colors = mode.colors();
+    
+    // 256색: 16번부터 빨강/초록/파랑 단계 (각 64단계)와 회색 48단계
+    if colors == 256 {
+        let mut rgb = [0u8; 240 * 3];
+        for (i, entry) in rgb.chunks_mut(3).enumerate() {
+            let level = (i % 64) as u8;
+            match i / 64 {
+                0 => entry[0] = level,
+                1 => entry[1] = level,
+                2 => entry[2] = level,
+                _ => entry.fill(((i - 192) * 63 / 47) as u8),
             }
         }
-        let bg = BG_COLOR;
-        for col in 0..80 {
-            *vga.offset(((24 * 80 + col) * 2) as isize) = b' ';
-            *vga.offset(((24 * 80 + col) * 2 + 1) as isize) = (bg << 4) | 0x0F;
+        palette::set_palette(16, &rgb);
+    }
+    
+    // 위쪽에 전체 색 띠
+    let band = (width / colors).max(1);
+    for color in 0..colors {
+        canvas.fill_rect((color * band) as i32, 0, band, h as usize / 8, color as u8);
+    }
+    
+    // 테두리, 가운데에서 뻗는 선
+    canvas.rect(0, 0, width, height, 15);
+    let (cx, cy) = (w / 2, h * 9 / 16);
+    for i in 0..16 {
+        let color = (i % 15 + 1) as u8;
+        canvas.line(cx, cy, i * (w - 1) / 15, h / 8 + 4, color);
+        canvas.line(cx, cy, i * (w - 1) / 15, h - 5, color);
+    }
+    canvas.fill_rect(8, h / 4, width / 6, height / 4, 4);
+    canvas.rect(8 + w / 12, h / 4 + h / 8, width / 6, height / 4, 14);
+    
+    // 블릿: 16x16 체크무늬 원을 오른쪽에 여러 개 (가장자리에서 잘림)
+    let mut sprite = [0u8; SPRITE * SPRITE];
+    for (i, pixel) in sprite.iter_mut().enumerate() {
+        let (x, y) = ((i % SPRITE) as i32 * 2 - 15, (i / SPRITE) as i32 * 2 - 15);
+        if x * x + y * y <= 225 {
+            *pixel = if (x / 6 + y / 6) % 2 == 0 { 14 } else { 9 };
+        }
+    }
+    for i in 0..4 {
+        canvas.blit(w - 3 * SPRITE as i32 + i * 12, h / 4 + i * 20, SPRITE, SPRITE, &sprite);
+    }
+    
+    // 256색 모드는 기본 콘솔이 프레임버퍼 콘솔 (맨 아래 글자 줄)
+    if mode == GraphicsMode::Mode320x200x256 {
+        console::with(|c| {
+            let (_, rows) = c.size();
+            c.set_cursor(1, rows - 1);
+            c.set_color(0x0F);
+            c.write_str("320x200x256 - press any key");
+        });
+    }
+    
+    loop {
+        if let InputEventKind::Key(event) = input.wait().kind {
+            if event.is_pressed() && event.ch.is_some() {
+                input::take_interrupt();
+                break;
+            }
         }
     }
+    console::leave_graphics();
 }
 
-fn scancode_to_char(scancode: u8) -> Option<char> {
-    // release 코드 무시 (0x80 이상)
-    if scancode & 0x80 != 0 {
-        return None;
+// 키 입력으로 입력 줄 편집 -> 다시 그려야 하면 true
+fn edit_line(shell: &mut Shell, ime: &mut HangulIme, event: &KeyEvent) -> bool {
+    match (event.code, event.ch) {
+        (_, Some(ch)) if hangul::is_jamo(ch) => {
+            if let Some(done) = ime.feed(ch) {
+                shell.add_char(done);
+            }
+            true
+        },
+        (_, Some('\x08')) if ime.backspace() => true,
+        (code, ch) if ch.is_some() || matches!(code,
+            KeyCode::Left | KeyCode::Right | KeyCode::Home | KeyCode::End | KeyCode::Delete |
+            KeyCode::HanEng | KeyCode::RightAlt | KeyCode::Space) => {
+            // 한글 이외의 키: 조합 중인 글자를 먼저 확정
+            let flushed = match ime.flush() {
+                Some(done) => { shell.add_char(done); true },
+                None => false,
+            };
+            let edited = match code {
+                KeyCode::Left => { shell.move_left(); true },
+                KeyCode::Right => { shell.move_right(); true },
+                KeyCode::Home => { shell.move_home(); true },
+                KeyCode::End => { shell.move_end(); true },
+                KeyCode::Delete => { shell.delete(); true },
+                _ => match ch {
+                    Some('\x08') => { shell.backspace(); true },
+                    Some(ch) if ch == ' ' || ch.is_ascii_graphic() => { shell.add_char(ch); true },
+                    _ => false,
+                },
+            };
+            flushed || edited
+        },
+        _ => false,
     }
+}
+
+// 입력 줄 다시 그리기 (조합 중인 한글은 커서 위치에 끼워서 표시)
+fn redraw_input(vt: usize, shell: &Shell, ime: &HangulIme) {
+    let text = shell.get_buffer();
+    let (before, after) = text.split_at(shell.cursor());
     
-    match scancode {
-        0x02 => Some('1'), 0x03 => Some('2'), 0x04 => Some('3'),
-        0x05 => Some('4'), 0x06 => Some('5'), 0x07 => Some('6'),
-        0x08 => Some('7'), 0x09 => Some('8'), 0x0A => Some('9'),
-        0x0B => Some('0'),
-        0x10 => Some('q'), 0x11 => Some('w'), 0x12 => Some('e'),
-        0x13 => Some('r'), 0x14 => Some('t'), 0x15 => Some('y'),
-        0x16 => Some('u'), 0x17 => Some('i'), 0x18 => Some('o'),
-        0x19 => Some('p'),
-        0x1E => Some('a'), 0x1F => Some('s'), 0x20 => Some('d'),
-        0x21 => Some('f'), 0x22 => Some('g'), 0x23 => Some('h'),
-        0x24 => Some('j'), 0x25 => Some('k'), 0x26 => Some('l'),
-        0x2C => Some('z'), 0x2D => Some('x'), 0x2E => Some('c'),
-        0x2F => Some('v'), 0x30 => Some('b'), 0x31 => Some('n'),
-        0x32 => Some('m'),
-        0x39 => Some(' '),
-        0x1C => Some('\n'),
-        0x0E => Some('\x08'),
-        _ => None,
+    let mut line = LineBuf::new();
+    let _ = line.write_str(before);
+    if let Some(ch) = ime.preedit() {
+        let _ = line.write_char(ch);
     }
+    let _ = line.write_str(after);
+    
+    draw_input_line(vt, line.as_str(), Some(before.chars().count()));
 }
 
-#[panic_handler]
-fn panic(_info: &PanicInfo) -> ! {
-    let vga = 0xb8000 as *mut u16;
-    for i in 0..(80 * 25) {
-        unsafe {
-            *vga.add(i) = (b' ' as u16) | (0x4F << 8);
+// 고정 위치 상태 줄 -> 커서는 다음 줄 처음
+fn status_line(vt: usize, row: usize, text: &str, color: u8) {
+    console::with_vt(vt, |c| c.set_cursor(0, row));
+    put_line(vt, text, color);
+    console::with_vt(vt, |c| c.set_cursor(0, row + 1));
+}
+
+// 한 줄 출력 후 다음 줄로
+fn put_line(vt: usize, text: &str, color: u8) {
+    console::with_vt(vt, |c| c.set_color(color));
+    print_vt(vt, text);
+    print_vt(vt, "\n");
+}
+
+// 보이는 터미널은 print!로 출력 (콘솔 계층이 시리얼에도 미러링), 나머지는 메모리에만
+fn print_vt(vt: usize, text: &str) {
+    if vt == console::active_vt() {
+        print!("{}", text);
+    } else {
+        console::with_vt(vt, |c| c.write_str(text));
+    }
+}
+
+// 확정된 입력 줄: 입력 줄 자리에 "> 내용"을 출력 (커서는 줄 끝)
+fn commit_input_line(vt: usize, text: &str) {
+    console::with_vt(vt, |c| {
+        let (_, row) = c.cursor();
+        c.clear_row(row);
+        c.set_cursor(0, row);
+        c.set_color(0x0F);
+        c.show_cursor(false);
+    });
+    print_vt(vt, "> ");
+    print_vt(vt, text);
+}
+
+// seq부터 level 이하의 커널 로그 출력
+fn show_log(session: &mut Session, mut seq: u64, level: log::LevelFilter) {
+    while let Some(entry) = klog::read(seq) {
+        if entry.level <= level {
+            let mut line = LineBuf::new();
+            entry.format(&mut line);
+            session.put_line(line.as_str(), klog::level_color(entry.level));
         }
+        seq += 1;
+    }
+}
+
+fn run_watch(session: &mut Session, command: shell::WatchCommand) {
+    match command {
+        shell::WatchCommand::List => {
+            let mut any = false;
+            for (slot, watch) in watchpoint::list().iter().enumerate() {
+                if let Some(w) = watch {
+                    any = true;
+                    let mut line = LineBuf::new();
+                    let _ = write!(line, "  {}: {:<2} {} bytes at {:#x}, {} hits",
+                        slot, w.kind.as_str(), w.len, w.addr, watchpoint::hit_count(slot));
+                    session.put_line(line.as_str(), 0x07);
+                }
+            }
+            if !any {
+                session.put_line("No watchpoints", 0x07);
+            }
+        },
+        shell::WatchCommand::Set(w) => match watchpoint::set(w) {
+            Some(slot) => {
+                let mut line = LineBuf::new();
+                let _ = write!(line, "Watchpoint {} set: {} {} bytes at {:#x}", slot, w.kind.as_str(), w.len, w.addr);
+                session.put_line(line.as_str(), 0x0A);
+            },
+            None => session.put_line("All 4 debug registers are in use", 0x0C),
+        },
+        shell::WatchCommand::Clear(slot) => {
+            if watchpoint::clear(slot) {
+                session.put_line("Watchpoint cleared", 0x0A);
+            } else {
+                session.put_line("No such watchpoint", 0x0C);
+            }
+        },
+    }
+}
+
+// 사이클 -> "12.345 ms"
+fn write_ms(line: &mut LineBuf, cycles: u64, per_ms: u64) {
+    let us = cycles * 1000 / per_ms;
+    let _ = write!(line, "{:4}.{:03} ms", us / 1000, us % 1000);
+}
+
+// 부팅 단계 타임라인: 시작 시점, 걸린 시간, 결과
+fn show_bootlog(session: &mut Session) {
+    let per_ms = bootlog::tsc_per_ms();
+    let mut line = LineBuf::new();
+    let _ = write!(line, "Boot timeline (TSC {} MHz)", per_ms / 1000);
+    session.put_line(line.as_str(), 0x0E);
+    session.put_line("     start      time  result  stage", 0x07);
+
+    for stage in bootlog::stages() {
+        let mut line = LineBuf::new();
+        let _ = write!(line, "+");
+        write_ms(&mut line, stage.start, per_ms);
+        let _ = write!(line, " ");
+        write_ms(&mut line, stage.cycles, per_ms);
+        let _ = write!(line, "  {}  {}", if stage.ok { "ok  " } else { "FAIL" }, stage.name);
+        session.put_line(line.as_str(), if stage.ok { 0x0A } else { 0x0C });
+    }
+
+    let mut line = LineBuf::new();
+    let _ = write!(line, "Total:");
+    write_ms(&mut line, bootlog::total_cycles(), per_ms);
+    session.put_line(line.as_str(), 0x0B);
+}
+
+const PROFILE_TOP: usize = 15;
+
+// 함수별 샘플 수 막대그래프
+fn show_profile(session: &mut Session) {
+    let report = profiler::report();
+    if report.total == 0 {
+        session.put_line("No samples. Run 'profile start' first", 0x0E);
+        return;
     }
-    let msg = b"!!! KERNEL PANIC !!!";
-    for (i, &byte) in msg.iter().enumerate() {
-        unsafe {
-            *vga.add(80 * 10 + 30 + i) = (byte as u16) | (0x4F << 8);
+    if !ksyms::is_loaded() {
+        session.put_line("No symbol table (run tools/ksyms on the kernel ELF)", 0x0E);
+    }
+
+    let mut line = LineBuf::new();
+    let _ = write!(line, "{} samples", report.total);
+    if report.dropped > 0 {
+        let _ = write!(line, " ({} dropped, buffer full)", report.dropped);
+    }
+    session.put_line(line.as_str(), 0x0B);
+
+    for hotspot in report.hotspots.iter().take(PROFILE_TOP) {
+        let percent = hotspot.count * 1000 / report.total;
+        let bar = "##########";
+        let mut line = LineBuf::new();
+        let _ = write!(line, "{:3}.{}% {:<10} ", percent / 10, percent % 10, &bar[..(percent / 100).max(1)]);
+        match hotspot.name {
+            Some(name) => { let _ = write!(line, "{}", name); },
+            None => { let _ = write!(line, "{:#x}", hotspot.addr); },
         }
+        session.put_line(line.as_str(), 0x07);
     }
+}
+
+// 입력 줄 그리기 (하드웨어 커서를 cursor 열로)
+fn draw_input_line(vt: usize, text: &str, cursor: Option<usize>) {
+    console::with_vt(vt, |c| {
+        let (width, _) = c.size();
+        let (_, row) = c.cursor();
+        c.clear_row(row);
+        c.set_cursor(0, row);
+        c.set_color(0x0F);
+        c.write_str("> ");
+        // 한 줄에 들어가는 만큼만 표시
+        let end = text.char_indices().nth(width - 2).map_or(text.len(), |(i, _)| i);
+        c.write_str(&text[..end]);
+        
+        // 확정된 줄(None)은 커서를 숨기고 줄 끝에 둠
+        if let Some(pos) = cursor {
+            c.set_cursor((2 + pos).min(width - 1), row);
+        }
+        c.show_cursor(cursor.is_some());
+    });
+}
+
+#[cfg(test)]
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    myos_kernel::test_panic_handler(info)
+}
+
+#[cfg(not(test))]
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    let regs = myos_kernel::crashdump::capture_registers();
+    myos_kernel::crashdump::dump("panic", format_args!("{}", info), &regs);
+    
+    console::crash_screen("!!! KERNEL PANIC !!!", format_args!("{}", info));
     loop { hlt(); }
 }
\ No newline at end of file
@@ -0,0 +1,178 @@
+// kernel/src/mouse.rs - PS/2 마우스 드라이버 (IRQ12, 두 번째 8042 포트)
+use spin::Mutex;
+use x86_64::instructions::port::Port;
+use crate::ps2::{self, Ps2Status};
+use crate::input::{self, InputDevice, InputEventKind};
+use log::{info, warn};
+
+// 마우스 명령
+const CMD_GET_ID: u8 = 0xF2;
+const CMD_SET_SAMPLE_RATE: u8 = 0xF3;
+const CMD_ENABLE_REPORTING: u8 = 0xF4;
+const CMD_SET_DEFAULTS: u8 = 0xF6;
+
+const ID_INTELLIMOUSE: u8 = 0x03;
+
+// 패킷 첫 바이트 플래그
+const FLAG_LEFT: u8 = 0x01;
+const FLAG_RIGHT: u8 = 0x02;
+const FLAG_MIDDLE: u8 = 0x04;
+const FLAG_ALWAYS_ONE: u8 = 0x08;
+const FLAG_X_SIGN: u8 = 0x10;
+const FLAG_Y_SIGN: u8 = 0x20;
+const FLAG_X_OVERFLOW: u8 = 0x40;
+const FLAG_Y_OVERFLOW: u8 = 0x80;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseButtons {
+    pub left: bool,
+    pub right: bool,
+    pub middle: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseEvent {
+    pub dx: i16,
+    pub dy: i16,    // 위쪽이 양수 (PS/2 좌표계)
+    pub wheel: i8,  // 아래로 굴리면 양수
+    pub buttons: MouseButtons,
+}
+
+// 3바이트(표준) / 4바이트(IntelliMouse) 패킷 조립기
+pub struct PacketDecoder {
+    packet: [u8; 4],
+    index: usize,
+    packet_size: usize,
+}
+
+impl Default for PacketDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PacketDecoder {
+    pub const fn new() -> Self {
+        PacketDecoder {
+            packet: [0; 4],
+            index: 0,
+            packet_size: 3,
+        }
+    }
+
+    pub fn set_wheel(&mut self, wheel: bool) {
+        self.packet_size = if wheel { 4 } else { 3 };
+        self.index = 0;
+    }
+
+    pub fn feed(&mut self, byte: u8) -> Option<MouseEvent> {
+        // 첫 바이트의 bit 3은 항상 1 -> 아니면 동기가 어긋난 것
+        if self.index == 0 && byte & FLAG_ALWAYS_ONE == 0 {
+            return None;
+        }
+
+        self.packet[self.index] = byte;
+        self.index += 1;
+        if self.index < self.packet_size {
+            return None;
+        }
+        self.index = 0;
+
+        let flags = self.packet[0];
+        // 오버플로 패킷은 값이 의미 없으므로 움직임 0 처리
+        let dx = if flags & FLAG_X_OVERFLOW != 0 {
+            0
+        } else {
+            self.packet[1] as i16 - (((flags & FLAG_X_SIGN) as i16) << 4)
+        };
+        let dy = if flags & FLAG_Y_OVERFLOW != 0 {
+            0
+        } else {
+            self.packet[2] as i16 - (((flags & FLAG_Y_SIGN) as i16) << 3)
+        };
+        // Z 값은 하위 4비트 부호 있는 정수
+        let wheel = if self.packet_size == 4 {
+            ((self.packet[3] << 4) as i8) >> 4
+        } else {
+            0
+        };
+
+        Some(MouseEvent {
+            dx,
+            dy,
+            wheel,
+            buttons: MouseButtons {
+                left: flags & FLAG_LEFT != 0,
+                right: flags & FLAG_RIGHT != 0,
+                middle: flags & FLAG_MIDDLE != 0,
+            },
+        })
+    }
+}
+
+static DECODER: Mutex<PacketDecoder> = Mutex::new(PacketDecoder::new());
+static PRESENT: Mutex<bool> = Mutex::new(false);
+static HAS_WHEEL: Mutex<bool> = Mutex::new(false);
+
+// 명령 전송 후 ACK 확인
+fn command(byte: u8) -> bool {
+    ps2::write_port2(byte);
+    ps2::read_port2() == Some(ps2::DEVICE_ACK)
+}
+
+fn set_sample_rate(rate: u8) -> bool {
+    command(CMD_SET_SAMPLE_RATE) && command(rate)
+}
+
+fn device_id() -> Option<u8> {
+    if command(CMD_GET_ID) {
+        ps2::read_port2()
+    } else {
+        None
+    }
+}
+
+// ps2::init() 이후, 인터럽트가 켜지기 전에 호출
+pub fn init(status: &Ps2Status) -> bool {
+    if !status.mouse_ok {
+        return false;
+    }
+    if !command(CMD_SET_DEFAULTS) {
+        warn!("mouse did not accept set defaults");
+        return false;
+    }
+
+    // IntelliMouse 감지: 샘플링 속도를 200, 100, 80 순서로 설정하면 ID가 3으로 바뀜
+    let wheel = set_sample_rate(200) && set_sample_rate(100) && set_sample_rate(80)
+        && device_id() == Some(ID_INTELLIMOUSE);
+    set_sample_rate(100);
+
+    if !command(CMD_ENABLE_REPORTING) {
+        warn!("mouse did not accept enable reporting");
+        return false;
+    }
+    info!("mouse enabled ({})", if wheel { "IntelliMouse, wheel" } else { "standard 3 byte" });
+
+    DECODER.lock().set_wheel(wheel);
+    *HAS_WHEEL.lock() = wheel;
+    *PRESENT.lock() = true;
+    true
+}
+
+pub fn is_present() -> bool {
+    *PRESENT.lock()
+}
+
+pub fn has_wheel() -> bool {
+    *HAS_WHEEL.lock()
+}
+
+// IRQ12 핸들러에서 호출 -> 입력 큐로 전달
+pub fn handle_interrupt() {
+    let mut port = Port::<u8>::new(0x60);
+    let byte: u8 = unsafe { port.read() };
+
+    if let Some(event) = DECODER.lock().feed(byte) {
+        input::push(InputDevice::Ps2Mouse, InputEventKind::Mouse(event));
+    }
+}
@@ -0,0 +1,49 @@
+// kernel/src/power.rs - 종료/재부팅 경로 (셸 명령, Ctrl+Alt+Del 공용)
+use x86_64::instructions::port::Port;
+use x86_64::instructions::{hlt, interrupts};
+
+// 화면 가운데에 한 줄 (콘솔을 잡고 있던 코드로는 돌아가지 않음)
+fn message(text: &str, color: u8) {
+    crate::console::emergency(|console| {
+        let (width, height) = console.size();
+        console.set_color(color);
+        console.clear();
+        console.set_cursor((width - text.len()) / 2, height / 2);
+        console.write_str(text);
+    });
+}
+
+pub fn shutdown() -> ! {
+    interrupts::disable();
+    message("Shutting down...", 0x0C);
+
+    // QEMU ACPI 전원 끄기
+    let mut port = Port::<u16>::new(0x604);
+    unsafe { port.write(0x2000); }
+    loop { hlt(); }
+}
+
+// 인터럽트 핸들러 안에서도 호출 가능 (반환하지 않음)
+pub fn reboot() -> ! {
+    interrupts::disable();
+    message("Rebooting...", 0x0E);
+
+    // 8042 컨트롤러의 CPU 리셋 라인 펄스
+    let mut status = Port::<u8>::new(0x64);
+    for _ in 0..100_000 {
+        if unsafe { status.read() } & 0x02 == 0 {
+            break;
+        }
+    }
+    unsafe { status.write(0xFE); }
+
+    // 실패하면 빈 IDT로 트리플 폴트 유도
+    unsafe {
+        use x86_64::structures::DescriptorTablePointer;
+        use x86_64::VirtAddr;
+        let empty = DescriptorTablePointer { limit: 0, base: VirtAddr::new(0) };
+        x86_64::instructions::tables::lidt(&empty);
+        core::arch::asm!("int3");
+    }
+    loop { hlt(); }
+}
@@ -0,0 +1,88 @@
+// kernel/src/profiler.rs - 타이머 인터럽트 기반 샘플링 프로파일러
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, Ordering};
+use spin::Mutex;
+use x86_64::instructions::interrupts;
+
+const MAX_SAMPLES: usize = 8192; // 100Hz로 약 80초
+
+struct Samples {
+    rips: [u64; MAX_SAMPLES],
+    len: usize,
+    dropped: u64,
+}
+
+static RUNNING: AtomicBool = AtomicBool::new(false);
+static SAMPLES: Mutex<Samples> = Mutex::new(Samples {
+    rips: [0; MAX_SAMPLES],
+    len: 0,
+    dropped: 0,
+});
+
+pub fn is_running() -> bool {
+    RUNNING.load(Ordering::Relaxed)
+}
+
+// 이전 샘플을 지우고 시작
+pub fn start() {
+    interrupts::without_interrupts(|| {
+        let mut samples = SAMPLES.lock();
+        samples.len = 0;
+        samples.dropped = 0;
+    });
+    RUNNING.store(true, Ordering::Relaxed);
+}
+
+pub fn stop() {
+    RUNNING.store(false, Ordering::Relaxed);
+}
+
+// 타이머 핸들러에서 호출 -> 인터럽트된 RIP 저장
+pub fn record(rip: u64) {
+    // 보고서 작성 중이면 기다리지 않고 버림
+    let Some(mut samples) = SAMPLES.try_lock() else {
+        return;
+    };
+    if samples.len < MAX_SAMPLES {
+        let len = samples.len;
+        samples.rips[len] = rip;
+        samples.len += 1;
+    } else {
+        samples.dropped += 1;
+    }
+}
+
+pub struct Hotspot {
+    pub addr: u64, // 함수 시작 주소 (심볼이 없으면 RIP 그대로)
+    pub name: Option<&'static str>,
+    pub count: usize,
+}
+
+pub struct Report {
+    pub total: usize,
+    pub dropped: u64,
+    pub hotspots: Vec<Hotspot>, // 샘플이 많은 순
+}
+
+// 샘플을 함수별로 모음
+pub fn report() -> Report {
+    let (rips, dropped) = interrupts::without_interrupts(|| {
+        let samples = SAMPLES.lock();
+        (samples.rips[..samples.len].to_vec(), samples.dropped)
+    });
+
+    let mut hotspots: Vec<Hotspot> = Vec::new();
+    for &rip in &rips {
+        let (addr, name) = match crate::ksyms::lookup(rip) {
+            Some(symbol) => (symbol.addr, Some(symbol.name)),
+            None => (rip, None),
+        };
+        match hotspots.iter_mut().find(|h| h.addr == addr) {
+            Some(hotspot) => hotspot.count += 1,
+            None => hotspots.push(Hotspot { addr, name, count: 1 }),
+        }
+    }
+    hotspots.sort_unstable_by(|a, b| b.count.cmp(&a.count).then(a.addr.cmp(&b.addr)));
+
+    Report { total: rips.len(), dropped, hotspots }
+}
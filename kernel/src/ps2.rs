@@ -0,0 +1,222 @@
+// kernel/src/ps2.rs - i8042 PS/2 컨트롤러 초기화 및 자체 테스트
+use x86_64::instructions::port::Port;
+use log::{info, warn};
+
+const DATA_PORT: u16 = 0x60;
+const STATUS_PORT: u16 = 0x64; // 쓰기 시 명령 포트
+
+const STATUS_OUTPUT_FULL: u8 = 0x01;
+const STATUS_INPUT_FULL: u8 = 0x02;
+const STATUS_AUX: u8 = 0x20; // 출력 버퍼의 바이트가 두 번째 포트(마우스)에서 옴
+
+// 컨트롤러 명령
+const CMD_READ_CONFIG: u8 = 0x20;
+const CMD_WRITE_CONFIG: u8 = 0x60;
+const CMD_DISABLE_PORT2: u8 = 0xA7;
+const CMD_ENABLE_PORT2: u8 = 0xA8;
+const CMD_TEST_PORT2: u8 = 0xA9;
+const CMD_SELF_TEST: u8 = 0xAA;
+const CMD_TEST_PORT1: u8 = 0xAB;
+const CMD_DISABLE_PORT1: u8 = 0xAD;
+const CMD_ENABLE_PORT1: u8 = 0xAE;
+const CMD_WRITE_PORT2: u8 = 0xD4;
+
+// 설정 바이트 비트
+const CONFIG_PORT1_IRQ: u8 = 0x01;
+const CONFIG_PORT2_IRQ: u8 = 0x02;
+const CONFIG_PORT1_CLOCK_OFF: u8 = 0x10;
+const CONFIG_PORT2_CLOCK_OFF: u8 = 0x20;
+const CONFIG_TRANSLATION: u8 = 0x40;
+
+// 장치 응답
+pub const DEVICE_ACK: u8 = 0xFA;
+pub const DEVICE_RESEND: u8 = 0xFE;
+const DEVICE_RESET: u8 = 0xFF;
+const DEVICE_SELF_TEST_OK: u8 = 0xAA;
+
+const TIMEOUT: u32 = 100_000;
+const RESET_TIMEOUT: u32 = 2_000_000; // 장치 리셋은 수백 ms 걸릴 수 있음
+
+#[derive(Debug, Clone, Copy)]
+pub struct Ps2Status {
+    pub controller_ok: bool,  // 0xAA -> 0x55
+    pub dual_channel: bool,   // 두 번째 포트 존재
+    pub port1_ok: bool,       // 0xAB -> 0x00
+    pub port2_ok: bool,       // 0xA9 -> 0x00
+    pub keyboard_ok: bool,    // 포트 1 장치 리셋 성공
+    pub mouse_ok: bool,       // 포트 2 장치 리셋 성공
+}
+
+fn status() -> u8 {
+    let mut port = Port::<u8>::new(STATUS_PORT);
+    unsafe { port.read() }
+}
+
+// 입력 버퍼가 비면 true
+fn wait_input_empty() -> bool {
+    for _ in 0..TIMEOUT {
+        if status() & STATUS_INPUT_FULL == 0 {
+            return true;
+        }
+    }
+    false
+}
+
+pub fn send_command(command: u8) {
+    wait_input_empty();
+    let mut port = Port::<u8>::new(STATUS_PORT);
+    unsafe { port.write(command); }
+}
+
+pub fn write_data(data: u8) {
+    wait_input_empty();
+    let mut port = Port::<u8>::new(DATA_PORT);
+    unsafe { port.write(data); }
+}
+
+// 두 번째 포트(마우스)로 바이트 전송
+pub fn write_port2(data: u8) {
+    send_command(CMD_WRITE_PORT2);
+    write_data(data);
+}
+
+// 컨트롤러나 첫 번째 포트(키보드)의 응답. 마우스 바이트는 버림
+pub fn read_data() -> Option<u8> {
+    read_data_timeout(false, TIMEOUT)
+}
+
+// 두 번째 포트(마우스)의 응답. 키보드 바이트는 버림
+pub fn read_port2() -> Option<u8> {
+    read_data_timeout(true, TIMEOUT)
+}
+
+fn read_data_timeout(aux: bool, timeout: u32) -> Option<u8> {
+    let mut port = Port::<u8>::new(DATA_PORT);
+    for _ in 0..timeout {
+        let status = status();
+        if status & STATUS_OUTPUT_FULL == 0 {
+            continue;
+        }
+        let byte = unsafe { port.read() };
+        if (status & STATUS_AUX != 0) == aux {
+            return Some(byte);
+        }
+    }
+    None
+}
+
+fn flush_output() {
+    let mut port = Port::<u8>::new(DATA_PORT);
+    for _ in 0..32 {
+        if status() & STATUS_OUTPUT_FULL == 0 {
+            break;
+        }
+        unsafe { port.read(); }
+    }
+}
+
+fn read_config() -> u8 {
+    send_command(CMD_READ_CONFIG);
+    read_data().unwrap_or(0)
+}
+
+fn write_config(config: u8) {
+    send_command(CMD_WRITE_CONFIG);
+    write_data(config);
+}
+
+// ACK(0xFA) 다음 자체 테스트 통과(0xAA)를 기다림
+fn wait_reset_ok(aux: bool) -> bool {
+    let mut acked = false;
+    while let Some(byte) = read_data_timeout(aux, RESET_TIMEOUT) {
+        match byte {
+            DEVICE_ACK => acked = true,
+            DEVICE_SELF_TEST_OK if acked => {
+                // 마우스는 장치 ID(0x00)를 이어서 보냄
+                let _ = read_data_timeout(aux, TIMEOUT);
+                return true;
+            },
+            _ => return false,
+        }
+    }
+    false
+}
+
+// 인터럽트가 켜지기 전에 호출해야 함 (폴링 방식)
+pub fn init() -> Ps2Status {
+    let mut result = Ps2Status {
+        controller_ok: false,
+        dual_channel: false,
+        port1_ok: false,
+        port2_ok: false,
+        keyboard_ok: false,
+        mouse_ok: false,
+    };
+
+    // 1. 두 포트 비활성화 후 출력 버퍼 비우기
+    send_command(CMD_DISABLE_PORT1);
+    send_command(CMD_DISABLE_PORT2);
+    flush_output();
+
+    // 2. 설정 바이트: IRQ 끄고 스캔코드 변환(세트 2 -> 1) 유지
+    let mut config = read_config();
+    let maybe_dual = config & CONFIG_PORT2_CLOCK_OFF != 0;
+    config &= !(CONFIG_PORT1_IRQ | CONFIG_PORT2_IRQ);
+    config |= CONFIG_TRANSLATION;
+    write_config(config);
+
+    // 3. 컨트롤러 자체 테스트 (일부 컨트롤러는 설정 바이트가 초기화됨)
+    send_command(CMD_SELF_TEST);
+    result.controller_ok = read_data() == Some(0x55);
+    write_config(config);
+
+    // 4. 두 번째 포트 존재 확인: 켜 보고 클럭 비트가 풀리는지 확인
+    if maybe_dual {
+        send_command(CMD_ENABLE_PORT2);
+        result.dual_channel = read_config() & CONFIG_PORT2_CLOCK_OFF == 0;
+        send_command(CMD_DISABLE_PORT2);
+    }
+
+    // 5. 포트 인터페이스 테스트
+    send_command(CMD_TEST_PORT1);
+    result.port1_ok = read_data() == Some(0x00);
+    if result.dual_channel {
+        send_command(CMD_TEST_PORT2);
+        result.port2_ok = read_data() == Some(0x00);
+    }
+
+    // 6. 포트 활성화 및 장치 리셋
+    if result.port1_ok {
+        send_command(CMD_ENABLE_PORT1);
+        write_data(DEVICE_RESET);
+        result.keyboard_ok = wait_reset_ok(false);
+        config |= CONFIG_PORT1_IRQ;
+        config &= !CONFIG_PORT1_CLOCK_OFF;
+    }
+    if result.port2_ok {
+        send_command(CMD_ENABLE_PORT2);
+        write_port2(DEVICE_RESET);
+        result.mouse_ok = wait_reset_ok(true);
+        config |= CONFIG_PORT2_IRQ;
+        config &= !CONFIG_PORT2_CLOCK_OFF;
+    }
+
+    // 7. IRQ 켜기
+    flush_output();
+    write_config(config);
+
+    let ok = |pass: bool| if pass { "OK" } else { "FAIL" };
+    if result.controller_ok && result.port1_ok && result.keyboard_ok {
+        info!("controller OK, port1 OK (kbd OK)");
+    } else {
+        warn!("controller {}, port1 {} (kbd {})",
+            ok(result.controller_ok), ok(result.port1_ok), ok(result.keyboard_ok));
+    }
+    if result.dual_channel {
+        info!("port2 {} (mouse {})", ok(result.port2_ok), ok(result.mouse_ok));
+    } else {
+        info!("no port2");
+    }
+
+    result
+}
@@ -0,0 +1,163 @@
+// kernel/src/serial.rs - COM1 시리얼 콘솔 (QEMU -serial stdio)
+use uart_16550::SerialPort;
+use spin::Mutex;
+use lazy_static::lazy_static;
+use core::fmt;
+use x86_64::instructions::port::Port;
+use crate::input::{self, InputDevice, InputEventKind};
+
+pub const COM1: u16 = 0x3F8;
+const LINE_STATUS: u16 = COM1 + 5;
+const LSR_DATA_READY: u8 = 0x01;
+
+const CTRL_C: u8 = 0x03;
+
+lazy_static! {
+    pub static ref SERIAL1: Mutex<SerialPort> = {
+        let mut serial_port = unsafe { SerialPort::new(COM1) };
+        serial_port.init();
+        Mutex::new(serial_port)
+    };
+}
+
+// IRQ4를 켜기 전에 호출 (UART 초기화 시 수신 인터럽트도 켜짐)
+pub fn init() {
+    lazy_static::initialize(&SERIAL1);
+}
+
+// IRQ4 핸들러에서 호출 -> 받은 바이트를 입력 큐로 전달
+pub fn handle_interrupt() {
+    let mut data = Port::<u8>::new(COM1);
+    let mut line_status = Port::<u8>::new(LINE_STATUS);
+
+    // FIFO에 쌓인 바이트를 모두 읽음
+    while unsafe { line_status.read() } & LSR_DATA_READY != 0 {
+        let byte = unsafe { data.read() };
+        if byte == CTRL_C {
+            input::request_interrupt();
+        }
+        input::push(InputDevice::Com1, InputEventKind::Serial(byte));
+    }
+}
+
+#[doc(hidden)]
+pub fn _print(args: fmt::Arguments) {
+    use core::fmt::Write;
+    // 인터럽트 핸들러에서도 출력하므로 잠금 중에는 인터럽트 차단
+    x86_64::instructions::interrupts::without_interrupts(|| {
+        SERIAL1.lock().write_fmt(args).expect("Printing to serial failed");
+    });
+}
+
+#[macro_export]
+macro_rules! serial_print {
+    ($($arg:tt)*) => ($crate::serial::_print(format_args!($($arg)*)));
+}
+
+#[macro_export]
+macro_rules! serial_println {
+    () => ($crate::serial_print!("\r\n"));
+    ($fmt:expr) => ($crate::serial_print!(concat!($fmt, "\r\n")));
+    ($fmt:expr, $($arg:tt)*) => ($crate::serial_print!(concat!($fmt, "\r\n"), $($arg)*));
+}
+
+// 시리얼 터미널을 콘솔로 사용 (위치/색은 ANSI 이스케이프로 전달)
+pub struct SerialConsole {
+    col: usize,
+    row: usize,
+}
+
+const SERIAL_COLS: usize = 80;
+const SERIAL_ROWS: usize = 25;
+
+// VGA 색 번호 -> SGR 번호 (밝은 색은 90번대/100번대)
+fn ansi_sgr(base: u8, color: u8) -> u8 {
+    let ansi = kernel_core::ansi::vga_to_ansi(color);
+    base + (ansi & 0x07) + if ansi & 0x08 != 0 { 60 } else { 0 }
+}
+
+impl SerialConsole {
+    const fn new() -> Self {
+        SerialConsole { col: 0, row: 0 }
+    }
+
+    fn send(&self, args: fmt::Arguments) {
+        use core::fmt::Write;
+        let _ = SERIAL1.lock().write_fmt(args);
+    }
+}
+
+impl crate::console::Console for SerialConsole {
+    fn size(&self) -> (usize, usize) {
+        (SERIAL_COLS, SERIAL_ROWS)
+    }
+
+    fn write_str(&mut self, s: &str) {
+        let mut port = SERIAL1.lock();
+        for ch in s.chars() {
+            match ch {
+                '\n' => {
+                    port.send_raw(b'\r');
+                    port.send_raw(b'\n');
+                    self.col = 0;
+                    self.row = (self.row + 1).min(SERIAL_ROWS - 1);
+                },
+                '\r' => { port.send_raw(b'\r'); self.col = 0; },
+                '\x08' => { port.send_raw(0x08); self.col = self.col.saturating_sub(1); },
+                ch => {
+                    let mut buf = [0; 4];
+                    for &byte in ch.encode_utf8(&mut buf).as_bytes() {
+                        port.send_raw(byte);
+                    }
+                    self.col += 1;
+                    if self.col >= SERIAL_COLS {
+                        self.col = 0;
+                        self.row = (self.row + 1).min(SERIAL_ROWS - 1);
+                    }
+                },
+            }
+        }
+    }
+
+    fn cursor(&self) -> (usize, usize) {
+        (self.col, self.row)
+    }
+
+    fn set_cursor(&mut self, col: usize, row: usize) {
+        self.col = col.min(SERIAL_COLS - 1);
+        self.row = row.min(SERIAL_ROWS - 1);
+        self.send(format_args!("\x1b[{};{}H", self.row + 1, self.col + 1));
+    }
+
+    fn set_color(&mut self, fg: u8) {
+        self.send(format_args!("\x1b[{}m", ansi_sgr(30, fg)));
+    }
+
+    fn set_background(&mut self, bg: u8) {
+        self.send(format_args!("\x1b[{}m", ansi_sgr(40, bg)));
+    }
+
+    fn clear_row(&mut self, row: usize) {
+        self.send(format_args!("\x1b[{};1H\x1b[2K", row.min(SERIAL_ROWS - 1) + 1));
+        self.set_cursor(self.col, self.row);
+    }
+
+    fn clear(&mut self) {
+        self.send(format_args!("\x1b[2J\x1b[H"));
+        self.col = 0;
+        self.row = 0;
+    }
+
+    fn show_cursor(&mut self, visible: bool) {
+        self.send(format_args!("\x1b[?25{}", if visible { 'h' } else { 'l' }));
+    }
+}
+
+impl fmt::Write for SerialConsole {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        crate::console::Console::write_str(self, s);
+        Ok(())
+    }
+}
+
+pub static SERIAL_CONSOLE: Mutex<SerialConsole> = Mutex::new(SerialConsole::new());
@@ -0,0 +1,117 @@
+// kernel/src/watchpoint.rs - DR0-DR3/DR7 하드웨어 워치포인트
+use core::arch::asm;
+use core::sync::atomic::{AtomicU64, Ordering};
+use spin::Mutex;
+use x86_64::instructions::interrupts;
+use x86_64::registers::debug::{
+    BreakpointCondition, BreakpointSize, DebugAddressRegister, DebugAddressRegisterNumber,
+    Dr0, Dr1, Dr2, Dr3, Dr6, Dr6Flags, Dr7, Dr7Flags, Dr7Value,
+};
+use kernel_core::watch::{Watch, WatchKind};
+
+pub const SLOTS: usize = 4;
+
+static WATCHES: Mutex<[Option<Watch>; SLOTS]> = Mutex::new([None; SLOTS]);
+static HITS: [AtomicU64; SLOTS] = [const { AtomicU64::new(0) }; SLOTS];
+
+fn number(slot: usize) -> DebugAddressRegisterNumber {
+    DebugAddressRegisterNumber::new(slot as u8).expect("debug register slot out of range")
+}
+
+fn write_addr(slot: usize, addr: u64) {
+    match slot {
+        0 => Dr0::write(addr),
+        1 => Dr1::write(addr),
+        2 => Dr2::write(addr),
+        _ => Dr3::write(addr),
+    }
+}
+
+fn condition(kind: WatchKind) -> BreakpointCondition {
+    match kind {
+        WatchKind::Write => BreakpointCondition::DataWrites,
+        WatchKind::ReadWrite => BreakpointCondition::DataReadsWrites,
+        WatchKind::Execute => BreakpointCondition::InstructionExecution,
+    }
+}
+
+// 슬롯 하나를 DR7에 반영
+fn apply(slot: usize, watch: Option<Watch>) {
+    let n = number(slot);
+    let mut dr7 = Dr7::read();
+    match watch {
+        Some(w) => {
+            write_addr(slot, w.addr);
+            dr7.set_condition(n, condition(w.kind));
+            dr7.set_size(n, BreakpointSize::new(w.len as usize).expect("length checked by Watch::new"));
+            dr7.insert_flags(Dr7Flags::local_breakpoint_enable(n));
+        },
+        None => dr7.remove_flags(Dr7Flags::local_breakpoint_enable(n)),
+    }
+    // 정확한 데이터 브레이크포인트 (LE)
+    let any = WATCHES.lock().iter().any(|w| w.is_some());
+    dr7.set_flags(Dr7Flags::LOCAL_EXACT_BREAKPOINT_ENABLE, any);
+    Dr7::write(dr7);
+}
+
+// 빈 디버그 레지스터에 설정 -> 번호 (모두 사용 중이면 None)
+pub fn set(watch: Watch) -> Option<usize> {
+    interrupts::without_interrupts(|| {
+        let slot = {
+            let mut watches = WATCHES.lock();
+            let slot = watches.iter().position(|w| w.is_none())?;
+            watches[slot] = Some(watch);
+            HITS[slot].store(0, Ordering::Relaxed);
+            slot
+        };
+        apply(slot, Some(watch));
+        Some(slot)
+    })
+}
+
+pub fn clear(slot: usize) -> bool {
+    interrupts::without_interrupts(|| {
+        if WATCHES.lock()[slot].take().is_none() {
+            return false;
+        }
+        apply(slot, None);
+        true
+    })
+}
+
+pub fn list() -> [Option<Watch>; SLOTS] {
+    interrupts::without_interrupts(|| *WATCHES.lock())
+}
+
+pub fn get(slot: usize) -> Option<Watch> {
+    WATCHES.lock()[slot]
+}
+
+pub fn hit_count(slot: usize) -> u64 {
+    HITS[slot].load(Ordering::Relaxed)
+}
+
+// #DB에서 호출: DR6를 읽고 다음 예외를 위해 비움
+pub fn take_status() -> Dr6Flags {
+    let status = Dr6::read();
+    unsafe {
+        // 예약 비트는 1로 유지
+        asm!("mov dr6, {}", in(reg) 0xFFFF_0FF0u64, options(nomem, nostack, preserves_flags));
+    }
+    status
+}
+
+// DR6에서 걸린 워치포인트 번호들
+pub fn triggered(status: Dr6Flags) -> impl Iterator<Item = usize> {
+    (0..SLOTS).filter(move |&slot| status.contains(Dr6Flags::trap(number(slot))))
+}
+
+pub fn record_hit(slot: usize) {
+    HITS[slot].fetch_add(1, Ordering::Relaxed);
+}
+
+// 실행 브레이크포인트면 재개 시 RF를 켜야 같은 명령에서 다시 걸리지 않음
+pub fn is_execute(status: Dr6Flags) -> bool {
+    let dr7: Dr7Value = Dr7::read();
+    triggered(status).any(|slot| dr7.condition(number(slot)) == BreakpointCondition::InstructionExecution)
+}
@@ -0,0 +1,64 @@
+// kernel/tests/debug_traps.rs - int3와 하드웨어 워치포인트가 보고 후 실행을 이어가는지 테스트
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![test_runner(myos_kernel::test_runner)]
+#![reexport_test_harness_main = "test_main"]
+
+use core::panic::PanicInfo;
+use core::ptr::{addr_of, addr_of_mut};
+use kernel_core::watch::{Watch, WatchKind};
+use myos_kernel::watchpoint;
+
+#[no_mangle]
+pub extern "C" fn _start() -> ! {
+    myos_kernel::init();
+    test_main();
+    myos_kernel::hlt_loop();
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    myos_kernel::test_panic_handler(info)
+}
+
+static mut TARGET: u64 = 0;
+
+#[test_case]
+fn int3_resumes() {
+    x86_64::instructions::interrupts::int3();
+}
+
+#[test_case]
+fn write_watchpoint_hits() {
+    let addr = addr_of!(TARGET) as u64;
+    let slot = watchpoint::set(Watch::new(addr, WatchKind::Write, 8).unwrap()).expect("no free debug register");
+
+    unsafe {
+        core::ptr::write_volatile(addr_of_mut!(TARGET), 1);
+        core::ptr::write_volatile(addr_of_mut!(TARGET), 2);
+    }
+    assert_eq!(watchpoint::hit_count(slot), 2);
+
+    // 읽기는 쓰기 워치포인트에 걸리지 않음
+    let value = unsafe { core::ptr::read_volatile(addr_of!(TARGET)) };
+    assert_eq!(value, 2);
+    assert_eq!(watchpoint::hit_count(slot), 2);
+
+    assert!(watchpoint::clear(slot));
+    unsafe { core::ptr::write_volatile(addr_of_mut!(TARGET), 3); }
+    assert_eq!(watchpoint::hit_count(slot), 2);
+}
+
+#[test_case]
+fn slots_run_out() {
+    let addr = addr_of!(TARGET) as u64;
+    let watch = Watch::new(addr, WatchKind::ReadWrite, 8).unwrap();
+    let slots: [Option<usize>; 4] = core::array::from_fn(|_| watchpoint::set(watch));
+    assert!(slots.iter().all(|s| s.is_some()));
+    assert_eq!(watchpoint::set(watch), None);
+    for slot in slots.into_iter().flatten() {
+        assert!(watchpoint::clear(slot));
+    }
+    assert!(watchpoint::list().iter().all(|w| w.is_none()));
+}
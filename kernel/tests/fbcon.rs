@@ -0,0 +1,91 @@
+// kernel/tests/fbcon.rs - 프레임버퍼 콘솔 통합 테스트 (메모리 버퍼를 8bpp 프레임버퍼로 사용)
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![test_runner(myos_kernel::test_runner)]
+#![reexport_test_harness_main = "test_main"]
+
+extern crate alloc;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::panic::PanicInfo;
+use myos_kernel::console::Console;
+use myos_kernel::fbcon::{Font, Framebuffer, FramebufferConsole};
+
+const WIDTH: usize = 32; // 4열
+const HEIGHT: usize = 24; // 3행
+
+// 'A'만 칸 전체가 켜진 글자, 나머지는 빈 글자
+static GLYPHS: [u8; 256 * 8] = {
+    let mut glyphs = [0; 256 * 8];
+    let mut i = 0;
+    while i < 8 {
+        glyphs[b'A' as usize * 8 + i] = 0xFF;
+        i += 1;
+    }
+    glyphs
+};
+
+#[no_mangle]
+pub extern "C" fn _start() -> ! {
+    myos_kernel::init();
+    test_main();
+    myos_kernel::hlt_loop();
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    myos_kernel::test_panic_handler(info)
+}
+
+fn console(pixels: &mut Vec<u8>) -> FramebufferConsole {
+    let fb = Framebuffer { base: pixels.as_mut_ptr(), width: WIDTH, height: HEIGHT, pitch: WIDTH, bpp: 8 };
+    unsafe { FramebufferConsole::new(fb, Font { glyphs: &GLYPHS, height: 8 }) }
+}
+
+// 칸 (col, row)의 모든 픽셀이 color인지
+fn cell_is(pixels: &[u8], col: usize, row: usize, color: u8) -> bool {
+    (0..8).all(|y| (0..8).all(|x| pixels[(row * 8 + y) * WIDTH + col * 8 + x] == color))
+}
+
+#[test_case]
+fn draws_glyphs() {
+    let mut pixels = vec![0xEE; WIDTH * HEIGHT];
+    let mut con = console(&mut pixels);
+    con.clear();
+    con.set_color(0x0E);
+    con.write_str("A ");
+    assert_eq!(con.size(), (4, 3));
+    assert_eq!(con.cursor(), (2, 0));
+    drop(con);
+    assert!(cell_is(&pixels, 0, 0, 0x0E));
+    assert!(cell_is(&pixels, 1, 0, 0x00));
+}
+
+#[test_case]
+fn wraps_at_line_end() {
+    let mut pixels = vec![0; WIDTH * HEIGHT];
+    let mut con = console(&mut pixels);
+    con.write_str("AAAAA");
+    assert_eq!(con.cursor(), (1, 1));
+    drop(con);
+    assert!(cell_is(&pixels, 3, 0, 0x07));
+    assert!(cell_is(&pixels, 0, 1, 0x07));
+    assert!(cell_is(&pixels, 1, 1, 0x00));
+}
+
+// 마지막 줄에서 줄바꿈하면 픽셀과 칸 내용이 한 줄씩 올라감
+#[test_case]
+fn scrolls_pixels_up() {
+    let mut pixels = vec![0; WIDTH * HEIGHT];
+    let mut con = console(&mut pixels);
+    con.write_str("\nA\n\n");
+    assert_eq!(con.cursor(), (0, 2));
+    // 배경색을 바꾸면 칸 내용으로 다시 그림 -> 옮겨진 칸 기록도 확인
+    con.set_background(0x01);
+    drop(con);
+    assert!(cell_is(&pixels, 0, 0, 0x07));
+    assert!(cell_is(&pixels, 0, 1, 0x01));
+    assert!(cell_is(&pixels, 0, 2, 0x01));
+}
@@ -0,0 +1,71 @@
+// kernel/tests/heap_allocation.rs - 힙 할당자 통합 테스트
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![test_runner(myos_kernel::test_runner)]
+#![reexport_test_harness_main = "test_main"]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::panic::PanicInfo;
+use myos_kernel::memory::HEAP_SIZE;
+
+#[no_mangle]
+pub extern "C" fn _start() -> ! {
+    myos_kernel::init();
+    test_main();
+    myos_kernel::hlt_loop();
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    myos_kernel::test_panic_handler(info)
+}
+
+#[test_case]
+fn simple_allocation() {
+    let a = Box::new(41);
+    let b = Box::new(13);
+    assert_eq!(*a, 41);
+    assert_eq!(*b, 13);
+}
+
+#[test_case]
+fn large_vec() {
+    let n = 1000u64;
+    let mut vec = Vec::new();
+    for i in 0..n {
+        vec.push(i);
+    }
+    assert_eq!(vec.iter().sum::<u64>(), (n - 1) * n / 2);
+}
+
+// 해제한 메모리를 다시 쓰지 못하면 힙 크기만큼 할당하다 실패함
+#[test_case]
+fn many_boxes() {
+    for i in 0..HEAP_SIZE {
+        let x = Box::new(i);
+        assert_eq!(*x, i);
+    }
+}
+
+#[test_case]
+fn many_boxes_long_lived() {
+    let long_lived = Box::new(1);
+    for i in 0..HEAP_SIZE {
+        let x = Box::new(i);
+        assert_eq!(*x, i);
+    }
+    assert_eq!(*long_lived, 1);
+}
+
+#[test_case]
+fn string_formatting() {
+    let mut text = String::from("Heap");
+    text.push_str(" OK!");
+    assert_eq!(text, "Heap OK!");
+    assert_eq!(alloc::format!("{}-{:02x}", 7, 255), "7-ff");
+}
@@ -0,0 +1,84 @@
+// kernel/tests/interrupts.rs - 타이머 IRQ와 키보드 입력 경로 테스트
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![test_runner(myos_kernel::test_runner)]
+#![reexport_test_harness_main = "test_main"]
+
+use core::panic::PanicInfo;
+use myos_kernel::input::{self, InputDevice, InputEventKind};
+use myos_kernel::interrupts;
+use myos_kernel::keyboard::{self, KeyCode};
+use x86_64::instructions::hlt;
+
+#[no_mangle]
+pub extern "C" fn _start() -> ! {
+    myos_kernel::init();
+    test_main();
+    myos_kernel::hlt_loop();
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    myos_kernel::test_panic_handler(info)
+}
+
+#[test_case]
+fn interrupts_enabled() {
+    assert!(interrupts::are_interrupts_enabled());
+}
+
+#[test_case]
+fn timer_ticks_advance() {
+    let start = interrupts::get_timer_ticks();
+    while interrupts::get_timer_ticks() < start + 3 {
+        hlt();
+    }
+    assert!(unsafe { myos_kernel::TICK_COUNTER } >= 3);
+}
+
+// IRQ1 핸들러가 부르는 경로 -> 입력 큐 구독자에게 전달
+#[test_case]
+fn keyboard_event_reaches_subscribers() {
+    let first = input::subscribe().expect("no free input subscriber slot");
+    let second = input::subscribe().expect("no free input subscriber slot");
+
+    keyboard::handle_scancode(0x1E); // A 누름
+    keyboard::handle_scancode(0x9E); // A 뗌
+
+    for subscriber in [&first, &second] {
+        let pressed = subscriber.poll().expect("missing press event");
+        assert_eq!(pressed.device, InputDevice::Ps2Keyboard);
+        match pressed.kind {
+            InputEventKind::Key(event) => {
+                assert_eq!(event.code, KeyCode::A);
+                assert!(event.is_pressed());
+                assert_eq!(event.ch, Some('a'));
+            },
+            _ => panic!("expected key event"),
+        }
+        assert!(matches!(subscriber.poll().map(|e| e.kind), Some(InputEventKind::Key(e)) if !e.is_pressed()));
+        assert!(subscriber.poll().is_none());
+    }
+}
+
+#[test_case]
+fn ctrl_c_requests_interrupt() {
+    input::take_interrupt();
+    keyboard::handle_scancode(0x1D); // 왼쪽 Ctrl
+    keyboard::handle_scancode(0x2E); // C
+    keyboard::handle_scancode(0xAE);
+    keyboard::handle_scancode(0x9D);
+    assert!(input::take_interrupt());
+    assert!(!input::take_interrupt());
+}
+
+#[test_case]
+fn subscriber_slots_are_released() {
+    let before = input::stats().subscribers;
+    {
+        let _subscriber = input::subscribe().expect("no free input subscriber slot");
+        assert_eq!(input::stats().subscribers, before + 1);
+    }
+    assert_eq!(input::stats().subscribers, before);
+}
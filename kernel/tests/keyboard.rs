@@ -0,0 +1,61 @@
+// kernel/tests/keyboard.rs - 키보드 드라이버 전역 상태와 LED 명령 테스트
+// 입력 큐 전달과 Ctrl+C는 tests/interrupts.rs에서 다룸
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![test_runner(myos_kernel::test_runner)]
+#![reexport_test_harness_main = "test_main"]
+
+use core::panic::PanicInfo;
+use myos_kernel::interrupts;
+use myos_kernel::keyboard::{self, LED_CAPS_LOCK};
+use myos_kernel::keymap;
+use x86_64::instructions::hlt;
+
+#[no_mangle]
+pub extern "C" fn _start() -> ! {
+    myos_kernel::init();
+    test_main();
+    myos_kernel::hlt_loop();
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    myos_kernel::test_panic_handler(info)
+}
+
+// 키보드가 leds를 ACK할 때까지 최대 0.5초 대기
+fn wait_leds(leds: u8) -> bool {
+    let end = interrupts::get_timer_ticks() + 50;
+    while interrupts::get_timer_ticks() < end {
+        if keyboard::leds() == Some(leds) {
+            return true;
+        }
+        hlt();
+    }
+    false
+}
+
+// Caps Lock을 누르면 0xED와 LED 바이트가 IRQ1으로 오는 ACK에 맞춰 끝까지 전송됨
+#[test_case]
+fn caps_lock_leds_are_acknowledged() {
+    let base = keyboard::modifiers().leds();
+    keyboard::process_scancode(0x3A);
+    keyboard::process_scancode(0xBA);
+    assert!(wait_leds(base | LED_CAPS_LOCK));
+    keyboard::process_scancode(0x3A);
+    keyboard::process_scancode(0xBA);
+    assert!(wait_leds(base));
+}
+
+#[test_case]
+fn layout_switch_is_global() {
+    keyboard::set_layout(&keymap::KOREAN);
+    assert_eq!(keyboard::layout().name, "ko");
+    keyboard::process_scancode(0xF2);
+    assert!(keyboard::hangul_mode());
+    keyboard::process_scancode(0xF2);
+    assert!(!keyboard::hangul_mode());
+    keyboard::set_layout(&keymap::US);
+    assert_eq!(keyboard::layout().name, "us");
+}
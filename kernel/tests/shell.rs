@@ -0,0 +1,71 @@
+// kernel/tests/shell.rs - 키보드 입력 경로로 입력한 줄의 Shell::execute 테스트
+// 명령 해석 자체는 kernel_core 호스트 테스트에서 다룸
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![test_runner(myos_kernel::test_runner)]
+#![reexport_test_harness_main = "test_main"]
+
+use core::panic::PanicInfo;
+use myos_kernel::input::{self, InputEventKind};
+use myos_kernel::interrupts;
+use myos_kernel::keyboard;
+use myos_kernel::shell::{Shell, ShellResult};
+use myos_kernel::TICK_COUNTER;
+use x86_64::instructions::hlt;
+
+#[no_mangle]
+pub extern "C" fn _start() -> ! {
+    myos_kernel::init();
+    test_main();
+    myos_kernel::hlt_loop();
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    myos_kernel::test_panic_handler(info)
+}
+
+// 스캔코드를 IRQ1 경로로 넣고, 입력 큐에서 받은 문자를 셸에 넣음 (Enter 전까지)
+fn type_line(shell: &mut Shell, scancodes: &[u8]) {
+    let subscriber = input::subscribe().expect("no free input subscriber slot");
+    for &scancode in scancodes {
+        keyboard::handle_scancode(scancode);
+        keyboard::handle_scancode(scancode | 0x80);
+    }
+    while let Some(event) = subscriber.poll() {
+        match event.kind {
+            InputEventKind::Key(key) if key.is_pressed() => match key.ch {
+                Some('\n') => return,
+                Some(ch) => shell.add_char(ch),
+                None => {},
+            },
+            _ => {},
+        }
+    }
+    panic!("no Enter in the input queue");
+}
+
+#[test_case]
+fn typed_command_executes() {
+    let mut shell = Shell::new();
+    // "sleep 2" Enter
+    type_line(&mut shell, &[0x1F, 0x26, 0x12, 0x12, 0x19, 0x39, 0x03, 0x1C]);
+    assert_eq!(shell.get_buffer(), "sleep 2");
+    assert!(matches!(shell.execute(unsafe { TICK_COUNTER }), ShellResult::Sleep(200)));
+    assert_eq!(shell.get_buffer(), "");
+}
+
+// uptime은 커널 타이머 틱 기준
+#[test_case]
+fn uptime_follows_timer_ticks() {
+    let mut shell = Shell::new();
+    shell.set_boot_time(unsafe { TICK_COUNTER });
+    let start = interrupts::get_timer_ticks();
+    while interrupts::get_timer_ticks() < start + 3 {
+        hlt();
+    }
+    // "uptime" Enter
+    type_line(&mut shell, &[0x16, 0x19, 0x14, 0x17, 0x32, 0x12, 0x1C]);
+    assert!(matches!(shell.execute(unsafe { TICK_COUNTER }), ShellResult::Uptime(ticks) if ticks >= 3));
+}
@@ -0,0 +1,120 @@
+// tools/crashdecode/src/main.rs - 커널 크래시 기록을 읽기 쉬운 보고서로 변환
+//
+// 사용법: crashdecode <커널 ELF> [시리얼 로그]   (로그를 생략하면 표준 입력)
+mod record;
+mod symbols;
+
+use std::io::Read;
+use std::process::ExitCode;
+use record::CrashRecord;
+use symbols::SymbolTable;
+
+const TICKS_PER_SECOND: u64 = 100;
+
+fn symbol(table: &SymbolTable, addr: u64) -> String {
+    table.lookup(addr).unwrap_or_else(|| "??".into())
+}
+
+fn print_report(record: &CrashRecord, table: &SymbolTable) {
+    println!("=== Kernel crash: {} ===", record.reason);
+    if !record.complete {
+        println!("(record truncated - serial output ended before the END marker)");
+    }
+    if let Some(nested) = &record.nested {
+        println!("(a {} happened while writing the dump)", nested);
+    }
+    println!("{}", record.message);
+    println!("at tick {} ({}.{:02}s after boot)",
+        record.tick, record.tick / TICKS_PER_SECOND, record.tick % TICKS_PER_SECOND);
+
+    if let Some(rip) = record.register("rip") {
+        println!();
+        println!("rip {:#018x}  {}", rip, symbol(table, rip));
+    }
+
+    println!();
+    println!("Registers:");
+    let general: Vec<_> = record.registers.iter().filter(|(name, _)| name != "rip").collect();
+    for row in general.chunks(3) {
+        let line: Vec<String> = row.iter()
+            .map(|(name, value)| format!("{:>6} {:016x}", name, value))
+            .collect();
+        println!(" {}", line.join("  "));
+    }
+
+    println!();
+    println!("Backtrace:");
+    for (depth, &addr) in record.backtrace.iter().enumerate() {
+        // 리턴 주소는 call 다음 명령이므로 1을 빼서 호출한 함수를 찾음
+        let lookup = if depth == 0 { addr } else { addr.wrapping_sub(1) };
+        println!("  {:2}: {:#018x}  {}", depth, addr, symbol(table, lookup));
+    }
+
+    println!();
+    println!("Stack:");
+    for &(addr, value) in &record.stack {
+        match table.lookup(value) {
+            Some(name) => println!("  {:016x}: {:016x}  <{}>", addr, value, name),
+            None => println!("  {:016x}: {:016x}", addr, value),
+        }
+    }
+
+    println!();
+    println!("Recent log:");
+    if record.log_locked {
+        println!("  (log buffer was locked)");
+    }
+    for line in &record.log {
+        println!("  [{:5}.{:02}] {:<5} {}: {}",
+            line.tick / TICKS_PER_SECOND, line.tick % TICKS_PER_SECOND,
+            line.level, line.target, line.message);
+    }
+
+    println!();
+    match &record.heap {
+        Some(heap) => println!("Heap: {} / {} bytes used ({} free)", heap.used, heap.size, heap.free),
+        None => println!("Heap: allocator was locked"),
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let (elf_path, log_path) = match args {
+        [elf] => (elf, None),
+        [elf, log] => (elf, Some(log)),
+        _ => return Err("usage: crashdecode <kernel-elf> [serial-log]".into()),
+    };
+
+    let elf = std::fs::read(elf_path).map_err(|e| format!("{}: {}", elf_path, e))?;
+    let table = SymbolTable::from_elf(&elf).map_err(|e| format!("{}: {}", elf_path, e))?;
+
+    let mut log = Vec::new();
+    match log_path {
+        Some(path) => log = std::fs::read(path).map_err(|e| format!("{}: {}", path, e))?,
+        None => {
+            std::io::stdin().read_to_end(&mut log).map_err(|e| format!("stdin: {}", e))?;
+        },
+    }
+
+    let records = record::parse_all(&String::from_utf8_lossy(&log))?;
+    if records.is_empty() {
+        return Err("no crash record found".into());
+    }
+    for (i, record) in records.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        print_report(record, &table);
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("crashdecode: {}", e);
+            ExitCode::FAILURE
+        },
+    }
+}
@@ -0,0 +1,223 @@
+// tools/crashdecode/src/record.rs - 시리얼 로그에서 크래시 기록 파싱 (kernel/src/crashdump.rs 형식)
+
+const BEGIN: &str = "-----BEGIN AEROGEL CRASH-----";
+const END: &str = "-----END AEROGEL CRASH-----";
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct LogLine {
+    pub tick: u64,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Heap {
+    pub size: usize,
+    pub used: usize,
+    pub free: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct CrashRecord {
+    pub version: u32,
+    pub reason: String,
+    pub message: String,
+    pub tick: u64,
+    pub registers: Vec<(String, u64)>,
+    pub stack: Vec<(u64, u64)>,
+    pub backtrace: Vec<u64>,
+    pub log: Vec<LogLine>,
+    pub log_locked: bool,
+    pub heap: Option<Heap>,
+    pub nested: Option<String>, // 덤프 중 다시 폴트가 난 경우
+    pub complete: bool, // END 줄까지 받았는지
+}
+
+impl CrashRecord {
+    pub fn register(&self, name: &str) -> Option<u64> {
+        self.registers.iter().find(|(n, _)| n == name).map(|&(_, v)| v)
+    }
+}
+
+fn hex(s: &str) -> Result<u64, String> {
+    u64::from_str_radix(s, 16).map_err(|_| format!("bad hex value {:?}", s))
+}
+
+fn dec<T: std::str::FromStr>(s: &str) -> Result<T, String> {
+    s.parse().map_err(|_| format!("bad number {:?}", s))
+}
+
+// "\\n" -> 줄바꿈, "\\\\" -> 역슬래시
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            match chars.next() {
+                Some('n') => out.push('\n'),
+                Some(other) => out.push(other),
+                None => out.push('\\'),
+            }
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+fn parse_line(record: &mut CrashRecord, line: &str) -> Result<(), String> {
+    let (key, rest) = line.split_once(' ').unwrap_or((line, ""));
+    let mut fields = rest.split_whitespace();
+    let mut next = |what: &str| fields.next().ok_or_else(|| format!("{}: missing {}", key, what));
+
+    match key {
+        "version" => record.version = dec(next("version")?)?,
+        "reason" => record.reason = rest.to_string(),
+        "message" => record.message = unescape(rest),
+        "tick" => record.tick = dec(next("tick")?)?,
+        "reg" => {
+            let name = next("name")?.to_string();
+            let value = hex(next("value")?)?;
+            record.registers.push((name, value));
+        },
+        "stack" => {
+            let addr = hex(next("address")?)?;
+            let value = hex(next("value")?)?;
+            record.stack.push((addr, value));
+        },
+        "bt" => {
+            next("depth")?;
+            record.backtrace.push(hex(next("address")?)?);
+        },
+        "log" if rest == "locked" => record.log_locked = true,
+        "log" => {
+            // log <틱> <수준> <대상> <메시지...>
+            let mut parts = rest.splitn(4, ' ');
+            let mut part = |what: &str| parts.next().ok_or_else(|| format!("log: missing {}", what));
+            let tick = dec(part("tick")?)?;
+            let level = part("level")?.to_string();
+            let target = part("target")?.to_string();
+            let message = unescape(parts.next().unwrap_or(""));
+            record.log.push(LogLine { tick, level, target, message });
+        },
+        "heap" if rest == "locked" => record.heap = None,
+        "heap" => {
+            record.heap = Some(Heap {
+                size: dec(next("size")?)?,
+                used: dec(next("used")?)?,
+                free: dec(next("free")?)?,
+            });
+        },
+        "nested" => record.nested = Some(rest.to_string()),
+        "" => {},
+        // 새 버전에서 추가된 항목은 무시
+        _ => {},
+    }
+    Ok(())
+}
+
+// 로그 전체에서 크래시 기록을 모두 찾음. 다른 시리얼 출력은 건너뜀
+pub fn parse_all(text: &str) -> Result<Vec<CrashRecord>, String> {
+    let mut records = Vec::new();
+    let mut current: Option<CrashRecord> = None;
+
+    for (number, raw) in text.lines().enumerate() {
+        let line = raw.trim_end_matches('\r');
+        if line == BEGIN {
+            // 앞 기록이 끝나지 않았으면 잘린 채로 보관
+            records.extend(current.take());
+            current = Some(CrashRecord::default());
+        } else if line == END {
+            if let Some(mut record) = current.take() {
+                record.complete = true;
+                records.push(record);
+            }
+        } else if let Some(record) = current.as_mut() {
+            parse_line(record, line).map_err(|e| format!("line {}: {}", number + 1, e))?;
+        }
+    }
+    records.extend(current);
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+boot noise
+[    0.01] INFO  kernel: [1/5] Initializing GDT...\r
+-----BEGIN AEROGEL CRASH-----\r
+version 1\r
+reason panic\r
+message panicked at src/main.rs:10:5:\\nboom \\\\o/\r
+tick 1234\r
+reg rip 0000000000201234\r
+reg rsp 000001000000fe00\r
+stack 000001000000fe00 0000000000201300\r
+stack 000001000000fe08 0000000000000000\r
+bt 0 0000000000201234\r
+bt 1 0000000000205678\r
+log 12 INFO ps2 Keyboard OK\r
+log 15 WARN mouse no wheel detected\r
+heap 204800 1024 203776\r
+-----END AEROGEL CRASH-----\r
+more noise
+";
+
+    #[test]
+    fn parses_sample() {
+        let records = parse_all(SAMPLE).unwrap();
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert!(record.complete);
+        assert_eq!(record.version, 1);
+        assert_eq!(record.reason, "panic");
+        assert_eq!(record.message, "panicked at src/main.rs:10:5:\nboom \\o/");
+        assert_eq!(record.tick, 1234);
+        assert_eq!(record.register("rip"), Some(0x201234));
+        assert_eq!(record.register("rax"), None);
+        assert_eq!(record.stack.len(), 2);
+        assert_eq!(record.backtrace, vec![0x201234, 0x205678]);
+        assert_eq!(record.log[1], LogLine {
+            tick: 15,
+            level: "WARN".into(),
+            target: "mouse".into(),
+            message: "no wheel detected".into(),
+        });
+        assert_eq!(record.heap, Some(Heap { size: 204800, used: 1024, free: 203776 }));
+    }
+
+    #[test]
+    fn truncated_and_nested() {
+        let text = "-----BEGIN AEROGEL CRASH-----\nreason double-fault\nbt 0 10\n\
+                    nested page-fault\n-----END AEROGEL CRASH-----\n\
+                    -----BEGIN AEROGEL CRASH-----\nreason panic\n";
+        let records = parse_all(text).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].nested.as_deref(), Some("page-fault"));
+        assert!(records[0].complete);
+        assert_eq!(records[1].reason, "panic");
+        assert!(!records[1].complete);
+    }
+
+    #[test]
+    fn locked_sections() {
+        let text = "-----BEGIN AEROGEL CRASH-----\nlog locked\nheap locked\n-----END AEROGEL CRASH-----\n";
+        let record = &parse_all(text).unwrap()[0];
+        assert!(record.log_locked);
+        assert_eq!(record.heap, None);
+    }
+
+    #[test]
+    fn reports_bad_lines() {
+        let text = "-----BEGIN AEROGEL CRASH-----\nreg rip xyz\n";
+        assert_eq!(parse_all(text).unwrap_err(), "line 2: bad hex value \"xyz\"");
+    }
+
+    #[test]
+    fn no_records() {
+        assert!(parse_all("just a normal boot\n").unwrap().is_empty());
+    }
+}
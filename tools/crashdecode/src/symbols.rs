@@ -0,0 +1,82 @@
+// tools/crashdecode/src/symbols.rs - 커널 ELF 심볼 테이블에서 주소 -> 함수 이름
+use object::{Object, ObjectSymbol, SymbolKind};
+
+struct Symbol {
+    addr: u64,
+    size: u64,
+    name: String,
+}
+
+pub struct SymbolTable {
+    symbols: Vec<Symbol>, // 주소 순
+}
+
+impl SymbolTable {
+    pub fn from_elf(data: &[u8]) -> Result<Self, String> {
+        let file = object::File::parse(data).map_err(|e| format!("not an ELF file: {}", e))?;
+        let symbols = file.symbols()
+            .filter(|sym| sym.kind() == SymbolKind::Text && sym.address() != 0)
+            .filter_map(|sym| {
+                let name = sym.name().ok()?;
+                Some((sym.address(), sym.size(), name))
+            });
+        let table = Self::from_symbols(symbols);
+        if table.symbols.is_empty() {
+            return Err("no function symbols (stripped kernel?)".into());
+        }
+        Ok(table)
+    }
+
+    fn from_symbols<'a>(symbols: impl Iterator<Item = (u64, u64, &'a str)>) -> Self {
+        let mut symbols: Vec<Symbol> = symbols
+            .map(|(addr, size, name)| Symbol {
+                addr,
+                size,
+                name: format!("{:#}", rustc_demangle::demangle(name)),
+            })
+            .collect();
+        symbols.sort_by_key(|sym| sym.addr);
+        SymbolTable { symbols }
+    }
+
+    // "함수+0x오프셋". 크기 정보가 있으면 범위 밖 주소는 None
+    pub fn lookup(&self, addr: u64) -> Option<String> {
+        let index = self.symbols.partition_point(|sym| sym.addr <= addr).checked_sub(1)?;
+        let sym = &self.symbols[index];
+        let offset = addr - sym.addr;
+        if sym.size != 0 && offset >= sym.size {
+            return None;
+        }
+        Some(format!("{}+{:#x}", sym.name, offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> SymbolTable {
+        SymbolTable::from_symbols([
+            (0x2000, 0x20, "_ZN11myos_kernel4main17h0123456789abcdefE"),
+            (0x1000, 0x100, "_start"),
+            (0x3000, 0, "trap_common"),
+        ].into_iter())
+    }
+
+    #[test]
+    fn demangles_and_offsets() {
+        let table = table();
+        assert_eq!(table.lookup(0x1000).as_deref(), Some("_start+0x0"));
+        assert_eq!(table.lookup(0x2010).as_deref(), Some("myos_kernel::main+0x10"));
+    }
+
+    #[test]
+    fn outside_symbols() {
+        let table = table();
+        assert_eq!(table.lookup(0x0fff), None);
+        assert_eq!(table.lookup(0x1100), None);
+        assert_eq!(table.lookup(0x2020), None);
+        // 크기를 모르는 심볼 (어셈블리 레이블)
+        assert_eq!(table.lookup(0x3040).as_deref(), Some("trap_common+0x40"));
+    }
+}
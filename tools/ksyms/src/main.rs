@@ -0,0 +1,138 @@
+// tools/ksyms/src/main.rs - 커널 ELF의 .ksyms 섹션에 심볼 테이블 기록 (kernel/src/ksyms.rs 형식)
+//
+// 사용법: ksyms <커널 ELF>   (cargo build 후, cargo bootimage 전에 실행)
+use object::{Object, ObjectSection, ObjectSymbol, SymbolKind};
+use std::process::ExitCode;
+
+const SECTION: &str = ".ksyms";
+const MAGIC: &[u8; 4] = b"KSYM";
+const HEADER_SIZE: usize = 8;
+const ENTRY_SIZE: usize = 16;
+const MAX_NAME: usize = 255; // 길이를 u8로 저장
+
+struct Symbol {
+    addr: u64,
+    size: u32,
+    name: String,
+}
+
+// UTF-8 경계를 지키며 자름
+fn truncate(name: &str, max: usize) -> &str {
+    let mut end = name.len().min(max);
+    while !name.is_char_boundary(end) {
+        end -= 1;
+    }
+    &name[..end]
+}
+
+fn collect_symbols(file: &object::File) -> Vec<Symbol> {
+    let mut symbols: Vec<Symbol> = file.symbols()
+        .filter(|sym| sym.kind() == SymbolKind::Text && sym.address() != 0)
+        .filter_map(|sym| {
+            let name = format!("{:#}", rustc_demangle::demangle(sym.name().ok()?));
+            Some(Symbol {
+                addr: sym.address(),
+                size: sym.size().min(u32::MAX as u64) as u32,
+                name: truncate(&name, MAX_NAME).to_string(),
+            })
+        })
+        .collect();
+    symbols.sort_by_key(|sym| sym.addr);
+    symbols.dedup_by_key(|sym| sym.addr);
+    symbols
+}
+
+// 테이블 직렬화 -> 공간이 모자라면 오류
+fn encode(symbols: &[Symbol], capacity: usize) -> Result<Vec<u8>, String> {
+    let names_start = HEADER_SIZE + symbols.len() * ENTRY_SIZE;
+    let mut entries = Vec::with_capacity(names_start);
+    let mut names = Vec::new();
+
+    entries.extend_from_slice(MAGIC);
+    entries.extend_from_slice(&(symbols.len() as u32).to_le_bytes());
+    for sym in symbols {
+        let name_at = (names_start + names.len()) as u32;
+        entries.extend_from_slice(&sym.addr.to_le_bytes());
+        entries.extend_from_slice(&sym.size.to_le_bytes());
+        entries.extend_from_slice(&name_at.to_le_bytes());
+        names.push(sym.name.len() as u8);
+        names.extend_from_slice(sym.name.as_bytes());
+    }
+    entries.extend_from_slice(&names);
+
+    if entries.len() > capacity {
+        return Err(format!("symbol table needs {} bytes but {} is only {} bytes (raise KSYMS_SIZE)",
+            entries.len(), SECTION, capacity));
+    }
+    entries.resize(capacity, 0);
+    Ok(entries)
+}
+
+fn run(path: &str) -> Result<(), String> {
+    let mut data = std::fs::read(path).map_err(|e| format!("{}: {}", path, e))?;
+
+    let (range, table, count) = {
+        let file = object::File::parse(&*data).map_err(|e| format!("{}: not an ELF file: {}", path, e))?;
+        let section = file.section_by_name(SECTION)
+            .ok_or_else(|| format!("{}: no {} section", path, SECTION))?;
+        let (offset, size) = section.file_range()
+            .ok_or_else(|| format!("{}: {} has no file data", path, SECTION))?;
+        let symbols = collect_symbols(&file);
+        let table = encode(&symbols, size as usize)?;
+        (offset as usize..(offset + size) as usize, table, symbols.len())
+    };
+
+    data[range].copy_from_slice(&table);
+    std::fs::write(path, &data).map_err(|e| format!("{}: {}", path, e))?;
+    println!("ksyms: wrote {} symbols to {}", count, path);
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [path] = args.as_slice() else {
+        eprintln!("usage: ksyms <kernel-elf>");
+        return ExitCode::FAILURE;
+    };
+    match run(path) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("ksyms: {}", e);
+            ExitCode::FAILURE
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(addr: u64, name: &str) -> Symbol {
+        Symbol { addr, size: 0x10, name: name.into() }
+    }
+
+    #[test]
+    fn encodes_layout() {
+        let table = encode(&[symbol(0x1000, "a"), symbol(0x2000, "bc")], 64).unwrap();
+        assert_eq!(table.len(), 64);
+        assert_eq!(&table[..4], b"KSYM");
+        assert_eq!(u32::from_le_bytes(table[4..8].try_into().unwrap()), 2);
+        // 둘째 항목: 주소, 크기, 이름 위치
+        assert_eq!(u64::from_le_bytes(table[24..32].try_into().unwrap()), 0x2000);
+        assert_eq!(u32::from_le_bytes(table[32..36].try_into().unwrap()), 0x10);
+        let name_at = u32::from_le_bytes(table[36..40].try_into().unwrap()) as usize;
+        assert_eq!(name_at, 8 + 2 * 16 + 2);
+        assert_eq!(&table[name_at..name_at + 3], b"\x02bc");
+    }
+
+    #[test]
+    fn too_small() {
+        assert!(encode(&[symbol(0x1000, "main")], 16).is_err());
+    }
+
+    #[test]
+    fn truncates_on_char_boundary() {
+        assert_eq!(truncate("한글", 4), "한");
+        assert_eq!(truncate("abc", 255), "abc");
+    }
+}
@@ -12,6 +12,7 @@ KERNEL_WIN_PATH=$(wslpath -w "$KERNEL_ABS")
 
 echo "Starting AerogelOS..."
 echo "Click on QEMU window to capture keyboard input"
+echo "GDB stub: run 'gdb' in the shell, then target remote localhost:4321"
 echo ""
 
 # 여러 옵션 추가
@@ -19,4 +20,6 @@ echo ""
     -drive format=raw,file="$KERNEL_WIN_PATH" \
     -m 512M \
     -serial stdio \
+    -serial tcp::4321,server,nowait \
+    -debugcon file:debugcon.log \
     -display sdl \
\ No newline at end of file